@@ -0,0 +1,301 @@
+//! This module provides checked arithmetic on the builtin integer types that
+//! reports failures using this crate's error types.
+//!
+//! The standard `checked_*` methods return an `Option`, which loses both the
+//! direction of an overflow and the operands involved. The [`ValueArith`]
+//! methods instead return an [`ArithError`] carrying both operands:
+//!
+//! - An overflow past `MAX` is a `PosOverflow`, and one past `MIN` is a
+//!   `NegOverflow`.
+//! - Division (or remainder) by zero is `Unrepresentable`.
+//!
+//! The right-hand operand may be of any type that can be converted into the
+//! left-hand operand's type with `ValueInto`. It is converted first, and if
+//! that conversion fails, its error kind is reported with both operands. This
+//! way, conversion and arithmetic share a single error type.
+//!
+//! `ArithError` implements `std::error::Error` whenever both operands
+//! implement `Debug`. Like the conversion errors, it implements
+//! [`ConvError`] and [`MapPayload`], with the pair of operands as its
+//! payload, and converts into a [`GeneralErrorKind`].
+//!
+//! # Examples
+//!
+//! ```
+//! # use conv2::*;
+//! use conv2::arith::{ArithError, ValueArith};
+//!
+//! assert_eq!(200u8.add_value(55u8), Ok(255));
+//! assert_eq!(200u8.add_value(56u8), Err(ArithError::PosOverflow((200, 56))));
+//! assert_eq!(5u32.sub_value(6u32), Err(ArithError::NegOverflow((5, 6))));
+//! assert_eq!(i32::MIN.div_value(-1i32), Err(ArithError::PosOverflow((i32::MIN, -1))));
+//! assert_eq!(7u16.rem_value(0u16), Err(ArithError::Unrepresentable((7, 0))));
+//!
+//! // Mixed types: the operand is converted first.
+//! assert_eq!(40u32.add_value(2i64), Ok(42));
+//! assert_eq!(40u32.add_value(-41i64), Err(ArithError::NegOverflow((40, -41))));
+//! assert_eq!(40u32.mul_value(1i64 << 40), Err(ArithError::PosOverflow((40, 1 << 40))));
+//!
+//! assert_eq!(
+//!     200u8.add_value(56u8).unwrap_err().to_string(),
+//!     "arithmetic resulted in positive overflow: (200, 56)"
+//! );
+//! ```
+
+use core::fmt::{self, Debug, Display};
+
+use crate::errors::{write_error, ConvError, GeneralError, GeneralErrorKind, MapPayload};
+use crate::ValueInto;
+
+const NEG_OVERFLOW: &str = "arithmetic resulted in negative overflow";
+const POS_OVERFLOW: &str = "arithmetic resulted in positive overflow";
+const UNREPRESENTABLE: &str = "arithmetic result is not representable";
+
+/// A failed arithmetic operation, carrying both operands.
+///
+/// The payload is the pair of operands, left-hand first. The right-hand
+/// operand is the value passed to the method, before being converted.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ArithError<L, R> {
+    /// The result was too negative for the type.
+    NegOverflow((L, R)),
+
+    /// The result was too positive for the type.
+    PosOverflow((L, R)),
+
+    /// The result was not representable, as when dividing by zero.
+    Unrepresentable((L, R)),
+}
+
+impl<L, R> ArithError<L, R> {
+    /// Builds an error of the given kind.
+    pub fn new(kind: GeneralErrorKind, lhs: L, rhs: R) -> Self {
+        match kind {
+            GeneralErrorKind::NegOverflow => ArithError::NegOverflow((lhs, rhs)),
+            GeneralErrorKind::PosOverflow => ArithError::PosOverflow((lhs, rhs)),
+            GeneralErrorKind::Unrepresentable => ArithError::Unrepresentable((lhs, rhs)),
+        }
+    }
+
+    /// Returns the kind of failure.
+    pub fn kind(&self) -> GeneralErrorKind {
+        match self {
+            ArithError::NegOverflow(..) => GeneralErrorKind::NegOverflow,
+            ArithError::PosOverflow(..) => GeneralErrorKind::PosOverflow,
+            ArithError::Unrepresentable(..) => GeneralErrorKind::Unrepresentable,
+        }
+    }
+
+    /// Returns the operands stored in this error.
+    pub fn into_inner(self) -> (L, R) {
+        match self {
+            ArithError::NegOverflow(v)
+            | ArithError::PosOverflow(v)
+            | ArithError::Unrepresentable(v) => v,
+        }
+    }
+}
+
+impl<L, R> Display for ArithError<L, R>
+where
+    L: Debug,
+    R: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArithError::NegOverflow(v) => write_error(f, "NegOverflow", NEG_OVERFLOW, Some(v)),
+            ArithError::PosOverflow(v) => write_error(f, "PosOverflow", POS_OVERFLOW, Some(v)),
+            ArithError::Unrepresentable(v) => {
+                write_error(f, "Unrepresentable", UNREPRESENTABLE, Some(v))
+            }
+        }
+    }
+}
+
+impl<L, R> std::error::Error for ArithError<L, R>
+where
+    L: Debug,
+    R: Debug,
+{
+}
+
+impl<L, R> ConvError for ArithError<L, R> {
+    type Payload = (L, R);
+
+    fn kind(&self) -> GeneralErrorKind {
+        ArithError::kind(self)
+    }
+
+    fn into_inner(self) -> (L, R) {
+        ArithError::into_inner(self)
+    }
+
+    fn as_inner(&self) -> &(L, R) {
+        match self {
+            ArithError::NegOverflow(v)
+            | ArithError::PosOverflow(v)
+            | ArithError::Unrepresentable(v) => v,
+        }
+    }
+}
+
+impl<L, R, L2, R2> MapPayload<(L2, R2)> for ArithError<L, R> {
+    type Output = ArithError<L2, R2>;
+
+    fn map_payload<F>(self, f: F) -> ArithError<L2, R2>
+    where
+        F: FnOnce((L, R)) -> (L2, R2),
+    {
+        match self {
+            ArithError::NegOverflow(v) => ArithError::NegOverflow(f(v)),
+            ArithError::PosOverflow(v) => ArithError::PosOverflow(f(v)),
+            ArithError::Unrepresentable(v) => ArithError::Unrepresentable(f(v)),
+        }
+    }
+}
+
+impl<L, R> From<ArithError<L, R>> for GeneralErrorKind {
+    fn from(e: ArithError<L, R>) -> Self {
+        e.kind()
+    }
+}
+
+impl<L, R> From<ArithError<L, R>> for GeneralError<(L, R)> {
+    fn from(e: ArithError<L, R>) -> Self {
+        match e {
+            ArithError::NegOverflow(v) => GeneralError::NegOverflow(v),
+            ArithError::PosOverflow(v) => GeneralError::PosOverflow(v),
+            ArithError::Unrepresentable(v) => GeneralError::Unrepresentable(v),
+        }
+    }
+}
+
+/// Checked arithmetic that reports failures as an [`ArithError`].
+///
+/// See the [module documentation](self) for details.
+pub trait ValueArith: Sized + Copy {
+    /// Checked addition.
+    fn add_value<Rhs>(self, rhs: Rhs) -> Result<Self, ArithError<Self, Rhs>>
+    where
+        Rhs: ValueInto<Self> + Copy,
+        Rhs::Err: ConvError;
+
+    /// Checked subtraction.
+    fn sub_value<Rhs>(self, rhs: Rhs) -> Result<Self, ArithError<Self, Rhs>>
+    where
+        Rhs: ValueInto<Self> + Copy,
+        Rhs::Err: ConvError;
+
+    /// Checked multiplication.
+    fn mul_value<Rhs>(self, rhs: Rhs) -> Result<Self, ArithError<Self, Rhs>>
+    where
+        Rhs: ValueInto<Self> + Copy,
+        Rhs::Err: ConvError;
+
+    /// Checked division, rounding towards zero.
+    fn div_value<Rhs>(self, rhs: Rhs) -> Result<Self, ArithError<Self, Rhs>>
+    where
+        Rhs: ValueInto<Self> + Copy,
+        Rhs::Err: ConvError;
+
+    /// Checked remainder, with the sign of `self`.
+    ///
+    /// Unlike `checked_rem`, `MIN.rem_value(-1)` succeeds, since the result
+    /// (zero) is representable.
+    fn rem_value<Rhs>(self, rhs: Rhs) -> Result<Self, ArithError<Self, Rhs>>
+    where
+        Rhs: ValueInto<Self> + Copy,
+        Rhs::Err: ConvError;
+}
+
+/// Converts the right-hand operand, reporting failures with both operands.
+fn convert_rhs<Lhs, Rhs>(lhs: Lhs, rhs: Rhs) -> Result<Lhs, ArithError<Lhs, Rhs>>
+where
+    Rhs: ValueInto<Lhs> + Copy,
+    Rhs::Err: ConvError,
+{
+    rhs.value_into()
+        .map_err(|e| ArithError::new(e.kind(), lhs, rhs))
+}
+
+macro_rules! value_arith_method {
+    ($name:ident, |$lhs:ident, $rhs:ident| $body:expr) => {
+        #[inline]
+        fn $name<Rhs>(self, rhs: Rhs) -> Result<Self, ArithError<Self, Rhs>>
+        where
+            Rhs: ValueInto<Self> + Copy,
+            Rhs::Err: ConvError,
+        {
+            let ($lhs, $rhs) = (self, convert_rhs(self, rhs)?);
+            let result: Result<Self, GeneralErrorKind> = $body;
+            result.map_err(|kind| ArithError::new(kind, self, rhs))
+        }
+    };
+}
+
+macro_rules! value_arith {
+    (signed: $($ity:ident),*) => {
+        $(
+            impl ValueArith for $ity {
+                value_arith_method!(add_value, |a, b| a.checked_add(b).ok_or(if b < 0 {
+                    GeneralErrorKind::NegOverflow
+                } else {
+                    GeneralErrorKind::PosOverflow
+                }));
+
+                value_arith_method!(sub_value, |a, b| a.checked_sub(b).ok_or(if b > 0 {
+                    GeneralErrorKind::NegOverflow
+                } else {
+                    GeneralErrorKind::PosOverflow
+                }));
+
+                value_arith_method!(mul_value, |a, b| a.checked_mul(b).ok_or(if (a < 0) != (b < 0) {
+                    GeneralErrorKind::NegOverflow
+                } else {
+                    GeneralErrorKind::PosOverflow
+                }));
+
+                // With a non-zero divisor, only `MIN / -1` can overflow.
+                value_arith_method!(div_value, |a, b| match b {
+                    0 => Err(GeneralErrorKind::Unrepresentable),
+                    _ => a.checked_div(b).ok_or(GeneralErrorKind::PosOverflow),
+                });
+
+                value_arith_method!(rem_value, |a, b| match b {
+                    0 => Err(GeneralErrorKind::Unrepresentable),
+                    _ => Ok(a.wrapping_rem(b)),
+                });
+            }
+        )*
+    };
+
+    (unsigned: $($uty:ident),*) => {
+        $(
+            impl ValueArith for $uty {
+                value_arith_method!(add_value, |a, b| a
+                    .checked_add(b)
+                    .ok_or(GeneralErrorKind::PosOverflow));
+
+                value_arith_method!(sub_value, |a, b| a
+                    .checked_sub(b)
+                    .ok_or(GeneralErrorKind::NegOverflow));
+
+                value_arith_method!(mul_value, |a, b| a
+                    .checked_mul(b)
+                    .ok_or(GeneralErrorKind::PosOverflow));
+
+                value_arith_method!(div_value, |a, b| a
+                    .checked_div(b)
+                    .ok_or(GeneralErrorKind::Unrepresentable));
+
+                value_arith_method!(rem_value, |a, b| a
+                    .checked_rem(b)
+                    .ok_or(GeneralErrorKind::Unrepresentable));
+            }
+        )*
+    };
+}
+
+value_arith! { signed: i8, i16, i32, i64, isize }
+value_arith! { unsigned: u8, u16, u32, u64, usize }
@@ -0,0 +1,228 @@
+//! This module provides float → integer conversions over whole slices.
+//!
+//! Converting element by element with `approx` checks and branches on every
+//! value, which stops the compiler from vectorizing the loop. The functions
+//! here split the input into chunks instead. Each chunk is range-checked
+//! first, combining the comparisons without branching, and then converted
+//! with a plain loop of casts. Both loops vectorize well on common targets,
+//! without any target-specific code.
+//!
+//! If a chunk fails the check, it is converted one element at a time to find
+//! the first failure, so the results always match those of `ApproxFrom`.
+//!
+//! - [`approx_slice`] fails on the first value that cannot be converted,
+//!   returning its index and the same error as `ApproxFrom`.
+//! - [`saturate_slice`] clamps out-of-range values to the bounds of the
+//!   destination type, like [`Saturate`], and only fails on NaN.
+//!
+//! On failure, `dst[..index]` holds the converted values, and the rest of
+//! `dst` is left unchanged.
+//!
+//! These are implemented for `f32` and `f64` into every builtin integer type,
+//! with the same schemes as `ApproxFrom`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use conv2::*;
+//! use conv2::batch::{approx_slice, saturate_slice};
+//!
+//! let src = [1.5f32, -2.5, 1e3, 7.0];
+//! let mut dst = [0i32; 4];
+//! assert_eq!(approx_slice::<_, _, RoundToNearest>(&src, &mut dst), Ok(()));
+//! assert_eq!(dst, [2, -3, 1000, 7]);
+//!
+//! let src = [1.0f32, 2.0, 300.0, f32::NAN];
+//! let mut dst = [0u8; 4];
+//! assert_eq!(
+//!     approx_slice::<_, _, DefaultApprox>(&src, &mut dst),
+//!     Err((2, FloatError::PosOverflow(300.0)))
+//! );
+//! assert_eq!(dst, [1, 2, 0, 0]);
+//!
+//! let src = [-1.0f64, 2.9, 1e9];
+//! let mut dst = [0u8; 3];
+//! assert_eq!(saturate_slice::<_, _, DefaultApprox>(&src, &mut dst), Ok(()));
+//! assert_eq!(dst, [0, 2, 255]);
+//! ```
+
+use crate::errors::{FloatError, Saturate, Unrepresentable};
+use crate::{
+    ApproxFrom, ApproxScheme, DefaultApprox, RoundToNearest, RoundToNegInf, RoundToPosInf,
+    RoundToZero,
+};
+
+/// The number of elements checked at once.
+///
+/// This is large enough to fill the vector registers several times over, and
+/// small enough that falling back to converting one element at a time stays
+/// cheap.
+const CHUNK: usize = 64;
+
+/// Converts every element of `src` into `dst`, stopping at the first value
+/// that cannot be converted.
+///
+/// On failure, returns the index of that value, and the error given by
+/// `ApproxFrom`. See the [module documentation](self) for details.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+#[inline]
+pub fn approx_slice<Src, Dst, Scheme>(
+    src: &[Src],
+    dst: &mut [Dst],
+) -> Result<(), (usize, FloatError<Src>)>
+where
+    Scheme: ApproxScheme,
+    Dst: BatchApproxFrom<Src, Scheme>,
+{
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination lengths differ"
+    );
+    Dst::batch_approx(src, dst)
+}
+
+/// Converts every element of `src` into `dst`, replacing values out of range
+/// with the nearest bound of `Dst`.
+///
+/// On failure, which only happens for NaN, returns the index of the first
+/// NaN. See the [module documentation](self) for details.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+#[inline]
+pub fn saturate_slice<Src, Dst, Scheme>(
+    src: &[Src],
+    dst: &mut [Dst],
+) -> Result<(), (usize, Unrepresentable<Src>)>
+where
+    Scheme: ApproxScheme,
+    Dst: BatchApproxFrom<Src, Scheme>,
+{
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination lengths differ"
+    );
+    Dst::batch_saturate(src, dst)
+}
+
+/// The float → integer conversions supported by [`approx_slice`] and
+/// [`saturate_slice`].
+///
+/// This trait cannot be implemented outside this crate.
+pub trait BatchApproxFrom<Src, Scheme = DefaultApprox>:
+    ApproxFrom<Src, Scheme, Err = FloatError<Src>> + sealed::Sealed<Src, Scheme>
+where
+    Scheme: ApproxScheme,
+{
+    #[doc(hidden)]
+    fn batch_approx(src: &[Src], dst: &mut [Self]) -> Result<(), (usize, FloatError<Src>)>;
+
+    #[doc(hidden)]
+    fn batch_saturate(src: &[Src], dst: &mut [Self]) -> Result<(), (usize, Unrepresentable<Src>)>;
+}
+
+mod sealed {
+    pub trait Sealed<Src, Scheme> {}
+}
+
+/// Converts a chunk that failed the check one element at a time, and returns
+/// the first failure.
+///
+/// The elements before it are written to `dst`.
+#[cold]
+fn fallback<Src, Dst, E, F>(src: &[Src], dst: &mut [Dst], offset: usize, conv: F) -> (usize, E)
+where
+    Src: Copy,
+    F: Fn(Src) -> Result<Dst, E>,
+{
+    for (i, (d, &s)) in dst.iter_mut().zip(src).enumerate() {
+        match conv(s) {
+            Ok(v) => *d = v,
+            Err(e) => return (offset + i, e),
+        }
+    }
+    unreachable!("the chunk failed the check, but every element converted")
+}
+
+macro_rules! batch_float2int {
+    ($src:ident => $($dst:ident),*) => {
+        $(
+            batch_float2int! { @ $src, $dst, DefaultApprox, |s| s }
+            batch_float2int! { @ $src, $dst, RoundToZero, |s| s }
+            batch_float2int! { @ $src, $dst, RoundToNearest, |s| s.round() }
+            batch_float2int! { @ $src, $dst, RoundToNegInf, |s| s.floor() }
+            batch_float2int! { @ $src, $dst, RoundToPosInf, |s| s.ceil() }
+        )*
+    };
+
+    (@ $src:ident, $dst:ident, $scheme:ident, |$s:ident| $round:expr) => {
+        impl sealed::Sealed<$src, $scheme> for $dst {}
+
+        impl BatchApproxFrom<$src, $scheme> for $dst {
+            fn batch_approx(
+                src: &[$src],
+                dst: &mut [$dst],
+            ) -> Result<(), (usize, FloatError<$src>)> {
+                let min = crate::SafeRange::<$src, $dst, $scheme>::MIN;
+                let max = crate::SafeRange::<$src, $dst, $scheme>::MAX;
+                for (n, (src, dst)) in src.chunks(CHUNK).zip(dst.chunks_mut(CHUNK)).enumerate() {
+                    // NaN fails both comparisons.
+                    let ok = src.iter().fold(true, |ok, &s| ok & (s >= min) & (s <= max));
+                    if !ok {
+                        return Err(fallback(src, dst, n * CHUNK, |s| {
+                            <$dst as ApproxFrom<$src, $scheme>>::approx_from(s)
+                        }));
+                    }
+                    for (d, &$s) in dst.iter_mut().zip(src) {
+                        // The check above guarantees that the rounded value
+                        // fits.
+                        *d = unsafe { $round.to_int_unchecked::<$dst>() };
+                    }
+                }
+                Ok(())
+            }
+
+            fn batch_saturate(
+                src: &[$src],
+                dst: &mut [$dst],
+            ) -> Result<(), (usize, Unrepresentable<$src>)> {
+                let min = crate::SafeRange::<$src, $dst, RoundToZero>::MIN;
+                let max = crate::SafeRange::<$src, $dst, RoundToZero>::MAX;
+                for (n, (src, dst)) in src.chunks(CHUNK).zip(dst.chunks_mut(CHUNK)).enumerate() {
+                    #[allow(clippy::eq_op)]
+                    let nan = src.iter().fold(false, |nan, &s| nan | (s != s));
+                    if nan {
+                        return Err(fallback(src, dst, n * CHUNK, |s| {
+                            <$dst as ApproxFrom<$src, $scheme>>::approx_from(s).saturate()
+                        }));
+                    }
+                    for (d, &$s) in dst.iter_mut().zip(src) {
+                        // Selecting the bounds here, then casting a value
+                        // known to fit, is much faster than `as`, which
+                        // saturates too but does not vectorize as well.
+                        let r = $round;
+                        let clamped = if r < min { min } else if r > max { max } else { r };
+                        let v = unsafe { clamped.to_int_unchecked::<$dst>() };
+                        *d = if r < min {
+                            $dst::MIN
+                        } else if r > max {
+                            $dst::MAX
+                        } else {
+                            v
+                        };
+                    }
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+batch_float2int! { f32 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize }
+batch_float2int! { f64 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize }
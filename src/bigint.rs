@@ -0,0 +1,279 @@
+//! This module provides conversions between the builtin numeric types and
+//! the arbitrary-precision `BigInt` and `BigUint` types from the `num-bigint`
+//! crate.
+//!
+//! The following conversions are provided:
+//!
+//! - `ValueFrom` from every primitive integer to `BigInt`, which always
+//!   succeeds, and to `BigUint`, which fails for negative values.
+//! - `ValueFrom` from `BigInt` and `BigUint` to every primitive integer,
+//!   which fails with `RangeError` (or `PosOverflow`, for `BigUint`) if the
+//!   value is out of range.
+//! - `ValueFrom` between `BigInt` and `BigUint`.
+//! - `ApproxFrom` from `BigInt` and `BigUint` to `f32` and `f64`. The
+//!   `RoundToNearest`, `RoundToZero`, `RoundToNegInf` and `RoundToPosInf`
+//!   schemes round as their names suggest; `DefaultApprox` rounds to nearest
+//!   with ties to even, as `as` does. Values whose rounded result does not
+//!   fit in the destination's finite range fail.
+//! - `ApproxFrom` from `f32` and `f64` to `BigInt` and `BigUint`. Every
+//!   finite value is converted exactly after rounding to an integer
+//!   (`DefaultApprox` truncates). NaN and infinities fail with `FloatError`.
+//!
+//! This module requires the `num-bigint` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! # use conv2::*;
+//! use num_bigint::BigInt;
+//!
+//! let big: BigInt = BigInt::from(1u64) << 64;
+//! assert_eq!(u64::value_from(big.clone() - 1u8), Ok(u64::MAX));
+//! assert_eq!(u64::value_from(big.clone()), Err(RangeError::PosOverflow(big.clone())));
+//!
+//! // 2^24 + 1 is not representable as an `f32`.
+//! let odd = BigInt::from(16_777_217);
+//! assert_eq!(<f32 as ApproxFrom<_, RoundToNegInf>>::approx_from(odd.clone()), Ok(16_777_216.0));
+//! assert_eq!(<f32 as ApproxFrom<_, RoundToPosInf>>::approx_from(odd), Ok(16_777_218.0));
+//!
+//! assert_eq!(<BigInt as ApproxFrom<_, RoundToZero>>::approx_from(1e30f64).unwrap().to_string(),
+//!            "1000000000000000019884624838656");
+//! ```
+
+use num_bigint::{BigInt, BigUint, Sign};
+
+use crate::errors::{FloatError, NegOverflow, NoError, PosOverflow, RangeError};
+
+/// How to round a magnitude that has more significant bits than a float's
+/// mantissa.
+#[derive(Copy, Clone)]
+enum Rounding {
+    /// Round to nearest, ties away from zero.
+    Nearest,
+    /// Round to nearest, ties to even.
+    NearestEven,
+    Zero,
+    NegInf,
+    PosInf,
+}
+
+/// Rounds a magnitude to `mant_bits` significant bits.
+///
+/// Returns `(mantissa, shift)` such that the rounded magnitude is
+/// `mantissa * 2^shift`, with `mantissa < 2^mant_bits`.
+fn round_magnitude(
+    mag: &BigUint,
+    negative: bool,
+    mant_bits: u64,
+    rounding: Rounding,
+) -> (u64, u64) {
+    let bits = mag.bits();
+    if bits <= mant_bits {
+        return (u64::try_from(mag).unwrap(), 0);
+    }
+
+    let mut shift = bits - mant_bits;
+    let mut mantissa = u64::try_from(mag >> shift).unwrap();
+    // `mag` is non-zero here, so it has a lowest set bit.
+    let lowest = mag.trailing_zeros().unwrap();
+    let inexact = lowest < shift;
+    let half = mag.bit(shift - 1);
+    let round_up = match rounding {
+        Rounding::Nearest => half,
+        Rounding::NearestEven => half && (lowest < shift - 1 || mantissa & 1 == 1),
+        Rounding::Zero => false,
+        Rounding::NegInf => negative && inexact,
+        Rounding::PosInf => !negative && inexact,
+    };
+    if round_up {
+        mantissa += 1;
+        if mantissa == 1 << mant_bits {
+            mantissa >>= 1;
+            shift += 1;
+        }
+    }
+    (mantissa, shift)
+}
+
+/// Builds `mantissa * 2^shift`, or `None` if it exceeds `f32::MAX`.
+fn compose_f32(mantissa: u64, shift: u64) -> Option<f32> {
+    if shift > 104 {
+        return None;
+    }
+    let scale = f32::from_bits((127 + shift as u32) << 23);
+    Some(mantissa as f32 * scale)
+}
+
+/// Builds `mantissa * 2^shift`, or `None` if it exceeds `f64::MAX`.
+fn compose_f64(mantissa: u64, shift: u64) -> Option<f64> {
+    if shift > 971 {
+        return None;
+    }
+    let scale = f64::from_bits((1023 + shift) << 52);
+    Some(mantissa as f64 * scale)
+}
+
+/// Converts an integral, finite float to a `BigInt` exactly.
+fn integral_to_bigint(src: f64) -> BigInt {
+    let bits = src.to_bits();
+    let sign = if bits >> 63 == 1 {
+        Sign::Minus
+    } else {
+        Sign::Plus
+    };
+    let exp = ((bits >> 52) & 0x7ff) as i64;
+    let frac = bits & ((1 << 52) - 1);
+    let (mantissa, exp) = if exp == 0 {
+        (frac, -1074)
+    } else {
+        (frac | (1 << 52), exp - 1075)
+    };
+    let mag = if exp >= 0 {
+        BigUint::from(mantissa) << exp as u64
+    } else {
+        // `src` is integral, so the discarded bits are all zero.
+        BigUint::from(mantissa.checked_shr((-exp) as u32).unwrap_or(0))
+    };
+    BigInt::from_biguint(sign, mag)
+}
+
+impl crate::ValueFrom<BigUint> for BigInt {
+    type Err = NoError;
+    #[inline]
+    fn value_from(src: BigUint) -> Result<BigInt, Self::Err> {
+        Ok(BigInt::from(src))
+    }
+}
+
+impl crate::ValueFrom<BigInt> for BigUint {
+    type Err = NegOverflow<BigInt>;
+    #[inline]
+    fn value_from(src: BigInt) -> Result<BigUint, Self::Err> {
+        match src.sign() {
+            Sign::Minus => Err(NegOverflow(src)),
+            _ => Ok(src.into_parts().1),
+        }
+    }
+}
+
+item_for_each! {
+    (i8), (i16), (i32), (i64), (isize), (u8), (u16), (u32), (u64), (usize) => {
+        ($ity:ident) => {
+            impl crate::ValueFrom<$ity> for BigInt {
+                type Err = NoError;
+                #[inline]
+                fn value_from(src: $ity) -> Result<BigInt, Self::Err> {
+                    Ok(BigInt::from(src))
+                }
+            }
+
+            impl crate::ValueFrom<$ity> for BigUint {
+                type Err = NegOverflow<$ity>;
+                #[inline]
+                #[allow(unused_comparisons)]
+                fn value_from(src: $ity) -> Result<BigUint, Self::Err> {
+                    if src < 0 {
+                        return Err(NegOverflow(src));
+                    }
+                    Ok(BigUint::from(src as u64))
+                }
+            }
+
+            impl crate::ValueFrom<BigInt> for $ity {
+                type Err = RangeError<BigInt>;
+                #[inline]
+                fn value_from(src: BigInt) -> Result<$ity, Self::Err> {
+                    match $ity::try_from(&src) {
+                        Ok(dst) => Ok(dst),
+                        Err(_) if src.sign() == Sign::Minus => Err(RangeError::NegOverflow(src)),
+                        Err(_) => Err(RangeError::PosOverflow(src)),
+                    }
+                }
+            }
+
+            impl crate::ValueFrom<BigUint> for $ity {
+                type Err = PosOverflow<BigUint>;
+                #[inline]
+                fn value_from(src: BigUint) -> Result<$ity, Self::Err> {
+                    $ity::try_from(&src).map_err(|_| PosOverflow(src))
+                }
+            }
+        };
+    }
+}
+
+macro_rules! bigint_float {
+    ($($scheme:ty => $rounding:expr, |$name:ident| $approx:expr;)*) => {
+        $(
+            bigint_float! { @float f32, 24, compose_f32; $scheme => $rounding, |$name| $approx }
+            bigint_float! { @float f64, 53, compose_f64; $scheme => $rounding, |$name| $approx }
+        )*
+    };
+
+    (@float $fty:ident, $mant_bits:expr, $compose:ident;
+        $scheme:ty => $rounding:expr, |$name:ident| $approx:expr) => {
+        impl crate::ApproxFrom<BigInt, $scheme> for $fty {
+            type Err = RangeError<BigInt>;
+            #[inline]
+            fn approx_from(src: BigInt) -> Result<$fty, Self::Err> {
+                let negative = src.sign() == Sign::Minus;
+                let (mantissa, shift) =
+                    round_magnitude(src.magnitude(), negative, $mant_bits, $rounding);
+                match $compose(mantissa, shift) {
+                    Some(dst) if negative => Ok(-dst),
+                    Some(dst) => Ok(dst),
+                    None if negative => Err(RangeError::NegOverflow(src)),
+                    None => Err(RangeError::PosOverflow(src)),
+                }
+            }
+        }
+
+        impl crate::ApproxFrom<BigUint, $scheme> for $fty {
+            type Err = PosOverflow<BigUint>;
+            #[inline]
+            fn approx_from(src: BigUint) -> Result<$fty, Self::Err> {
+                let (mantissa, shift) = round_magnitude(&src, false, $mant_bits, $rounding);
+                $compose(mantissa, shift).ok_or(PosOverflow(src))
+            }
+        }
+
+        impl crate::ApproxFrom<$fty, $scheme> for BigInt {
+            type Err = FloatError<$fty>;
+            #[inline]
+            fn approx_from(src: $fty) -> Result<BigInt, Self::Err> {
+                if src.is_nan() {
+                    return Err(FloatError::NotANumber(src));
+                }
+                if src.is_infinite() {
+                    return Err(if src < 0.0 {
+                        FloatError::NegOverflow(src)
+                    } else {
+                        FloatError::PosOverflow(src)
+                    });
+                }
+                let approx = { let $name = src; $approx };
+                Ok(integral_to_bigint(approx as f64))
+            }
+        }
+
+        impl crate::ApproxFrom<$fty, $scheme> for BigUint {
+            type Err = FloatError<$fty>;
+            #[inline]
+            fn approx_from(src: $fty) -> Result<BigUint, Self::Err> {
+                let approx = <BigInt as crate::ApproxFrom<$fty, $scheme>>::approx_from(src)?;
+                match approx.into_parts() {
+                    (Sign::Minus, _) => Err(FloatError::NegOverflow(src)),
+                    (_, mag) => Ok(mag),
+                }
+            }
+        }
+    };
+}
+
+bigint_float! {
+    crate::DefaultApprox => Rounding::NearestEven, |src| src.trunc();
+    crate::RoundToNearest => Rounding::Nearest, |src| src.round();
+    crate::RoundToZero => Rounding::Zero, |src| src.trunc();
+    crate::RoundToNegInf => Rounding::NegInf, |src| src.floor();
+    crate::RoundToPosInf => Rounding::PosInf, |src| src.ceil();
+}
@@ -0,0 +1,206 @@
+//! This module provides [`Bounded`], an integer restricted to a custom range.
+//!
+//! Often the valid range of a value is narrower than its type: a percentage
+//! stored in a `u8` is limited to `0..=100`, and a TCP port in a `u16` cannot
+//! be zero. `Bounded<T, MIN, MAX>` holds a `T` that is known to lie in
+//! `MIN..=MAX`, and takes part in the conversion traits:
+//!
+//! - `ValueFrom` and `ApproxFrom` convert into `T` as usual, then check the
+//!   custom bounds. Values outside them are reported as a `NegOverflow` or
+//!   `PosOverflow`, just like values outside `T` itself.
+//! - `Saturated` returns the custom bounds, so `unwrap_or_saturate` and
+//!   `saturate` clamp to `MIN..=MAX`.
+//! - [`Ranged`] is implemented too, so `widen` and `narrow` convert between
+//!   `Bounded` types, and to and from the types in [`ranged`].
+//!   `ValueFrom` converts to and from those types as well; see that module
+//!   for details.
+//!
+//! The bounds are given as `i128`, so that one type covers every integer
+//! type. They must lie within `T`, with `MIN <= MAX`; otherwise, any use of
+//! the type fails to compile.
+//!
+//! # Examples
+//!
+//! ```
+//! # use conv2::*;
+//! use conv2::bounded::Bounded;
+//!
+//! type Percent = Bounded<u8, 0, 100>;
+//! type Port = Bounded<u16, 1, 65535>;
+//!
+//! assert_eq!(Percent::value_from(42i32).map(Percent::get), Ok(42));
+//! assert_eq!(Percent::value_from(101i32), Err(RangeError::PosOverflow(101)));
+//! assert_eq!(Percent::value_from(-1i32), Err(RangeError::NegOverflow(-1)));
+//! assert_eq!(Port::value_from(0u32), Err(RangeError::NegOverflow(0)));
+//! assert_eq!(Port::value_from(70_000u32), Err(RangeError::PosOverflow(70_000)));
+//!
+//! // Approximations round first, then check the bounds.
+//! let p: Result<Percent, _> = 99.9f64.approx_by::<RoundToNearest>();
+//! assert_eq!(p.map(Percent::get), Ok(100));
+//!
+//! // Saturating clamps to the custom bounds.
+//! assert_eq!(Percent::value_from(250u8).unwrap_or_saturate().get(), 100);
+//! assert_eq!(Port::value_from(0i64).unwrap_or_saturate().get(), 1);
+//!
+//! // Converting between custom ranges.
+//! use conv2::ranged::Ranged;
+//! let p = Percent::new(42).unwrap();
+//! assert_eq!(p.widen::<Bounded<i16, -100, 100>>().get(), 42);
+//! assert_eq!(p.narrow::<Bounded<u8, 0, 9>>(), Err(RangeError::PosOverflow(p)));
+//! ```
+
+use std::fmt;
+
+use crate::errors::{FloatError, RangeError};
+use crate::misc::Saturated;
+use crate::ranged::{self, check, sealed, Ranged};
+use crate::{ApproxFrom, ApproxScheme, ValueFrom};
+
+/// An integer of type `T`, restricted to `MIN..=MAX`.
+///
+/// See the [module documentation](self) for details.
+///
+/// Bounds outside `T` are rejected at compile time:
+///
+/// ```compile_fail
+/// use conv2::bounded::Bounded;
+///
+/// let x = Bounded::<u8, 0, 300>::new(5);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bounded<T, const MIN: i128, const MAX: i128>(T);
+
+impl<T, const MIN: i128, const MAX: i128> Bounded<T, MIN, MAX> {
+    /// Returns the value.
+    #[inline]
+    pub fn get(self) -> T {
+        self.0
+    }
+}
+
+impl<T, const MIN: i128, const MAX: i128> fmt::Display for Bounded<T, MIN, MAX>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+macro_rules! bounded_from {
+    ($repr:ident; RangeError: $($src:ident),*) => {
+        $(
+            impl<const MIN: i128, const MAX: i128> ValueFrom<$src> for Bounded<$repr, MIN, MAX> {
+                type Err = RangeError<$src>;
+                #[inline]
+                fn value_from(src: $src) -> Result<Self, Self::Err> {
+                    check($repr::value_from(src)? as i128, src)
+                }
+            }
+
+            bounded_from! { @approx $repr; RangeError: $src }
+        )*
+    };
+
+    ($repr:ident; FloatError: $($src:ident),*) => {
+        $(
+            bounded_from! { @approx $repr; FloatError: $src }
+        )*
+    };
+
+    (@approx $repr:ident; $err:ident: $src:ident) => {
+        impl<Scheme, const MIN: i128, const MAX: i128> ApproxFrom<$src, Scheme>
+            for Bounded<$repr, MIN, MAX>
+        where
+            Scheme: ApproxScheme,
+            $repr: ApproxFrom<$src, Scheme>,
+            $err<$src>: From<<$repr as ApproxFrom<$src, Scheme>>::Err>,
+        {
+            type Err = $err<$src>;
+            #[inline]
+            fn approx_from(src: $src) -> Result<Self, Self::Err> {
+                check($repr::approx_from(src)? as i128, src)
+            }
+        }
+    };
+}
+
+macro_rules! bounded {
+    ($($repr:ident),*) => {
+        $(
+            impl<const MIN: i128, const MAX: i128> Bounded<$repr, MIN, MAX> {
+                /// The smallest allowed value.
+                pub const MIN: $repr = {
+                    assert!(
+                        MIN >= $repr::MIN as i128 && MIN <= MAX && MAX <= $repr::MAX as i128,
+                        "invalid bounds"
+                    );
+                    MIN as $repr
+                };
+
+                /// The largest allowed value.
+                pub const MAX: $repr = {
+                    let _ = Self::MIN;
+                    MAX as $repr
+                };
+
+                /// Returns the value if it is within the bounds, or `None`.
+                #[inline]
+                pub const fn new(value: $repr) -> Option<Self> {
+                    if value < Self::MIN || value > Self::MAX {
+                        None
+                    } else {
+                        Some(Bounded(value))
+                    }
+                }
+            }
+
+            impl<const MIN: i128, const MAX: i128> sealed::Sealed for Bounded<$repr, MIN, MAX> {
+                #[inline]
+                fn to_i128(self, _: sealed::Token) -> i128 {
+                    self.0 as i128
+                }
+
+                #[inline]
+                fn from_i128_unchecked(value: i128, _: sealed::Token) -> Self {
+                    Bounded(value as $repr)
+                }
+            }
+
+            impl<const MIN: i128, const MAX: i128> Ranged for Bounded<$repr, MIN, MAX> {
+                type Repr = $repr;
+                const LOWER: i128 = Self::MIN as i128;
+                const UPPER: i128 = Self::MAX as i128;
+
+                #[inline]
+                fn get(self) -> $repr {
+                    self.0
+                }
+            }
+
+            impl<const MIN: i128, const MAX: i128> From<Bounded<$repr, MIN, MAX>> for $repr {
+                #[inline]
+                fn from(value: Bounded<$repr, MIN, MAX>) -> Self {
+                    value.0
+                }
+            }
+
+            impl<const MIN: i128, const MAX: i128> Saturated for Bounded<$repr, MIN, MAX> {
+                #[inline]
+                fn saturated_max() -> Self {
+                    ranged::saturated_max()
+                }
+
+                #[inline]
+                fn saturated_min() -> Self {
+                    ranged::saturated_min()
+                }
+            }
+
+            bounded_from! { $repr; RangeError: i8, i16, i32, i64, isize, u8, u16, u32, u64, usize }
+            bounded_from! { $repr; FloatError: f32, f64 }
+        )*
+    };
+}
+
+bounded! { i8, i16, i32, i64, isize, u8, u16, u32, u64, usize }
@@ -0,0 +1,276 @@
+//! This module provides conversions between `num-complex`'s `Complex` types
+//! over the builtin numeric types.
+//!
+//! Complex numbers are converted component-wise, using the scalar conversion
+//! between the component types:
+//!
+//! - `ValueFrom<Complex<S>> for Complex<D>` is implemented wherever
+//!   `D: ValueFrom<S>` is.
+//! - `ApproxFrom<Complex<S>, Scheme> for Complex<D>` is implemented for every
+//!   pair of builtin numeric types, wherever `D: ApproxFrom<S, Scheme>` is.
+//!
+//! If either component fails to convert, the error is a [`ComplexError`]
+//! that records which component failed, along with the scalar error. The real
+//! part is converted first.
+//!
+//! Real scalars can also be converted into complex numbers with a zero
+//! imaginary part, using `ValueFrom<S> for Complex<D>` and
+//! `ApproxFrom<S, Scheme> for Complex<D>`. These produce the scalar error
+//! directly.
+//!
+//! This module requires the `num-complex` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! # use conv2::*;
+//! use conv2::complex::ComplexError;
+//! use num_complex::Complex;
+//!
+//! let z = Complex::new(0.5f64, -40_000.0);
+//! assert_eq!(<Complex<f32> as ApproxFrom<_>>::approx_from(z), Ok(Complex::new(0.5, -40_000.0)));
+//! assert_eq!(
+//!     <Complex<i16> as ApproxFrom<_, RoundToNearest>>::approx_from(z),
+//!     Err(ComplexError::Im(FloatError::NegOverflow(-40_000.0)))
+//! );
+//!
+//! assert_eq!(Complex::<i32>::value_from(Complex::new(1i16, -1)), Ok(Complex::new(1, -1)));
+//! assert_eq!(Complex::<f64>::value_from(3u8), Ok(Complex::new(3.0, 0.0)));
+//! ```
+
+use std::fmt::{self, Debug, Display};
+
+use num_complex::Complex;
+
+use crate::errors::{ConvError, GeneralErrorKind, MapPayload};
+
+/// Indicates which component of a complex number failed to convert.
+///
+/// The wrapped value is the error produced by the scalar conversion.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ComplexError<E> {
+    /// The real part failed to convert.
+    Re(E),
+
+    /// The imaginary part failed to convert.
+    Im(E),
+}
+
+impl<E> ComplexError<E> {
+    /// Returns the error produced by the scalar conversion.
+    pub fn into_error(self) -> E {
+        match self {
+            ComplexError::Re(e) | ComplexError::Im(e) => e,
+        }
+    }
+
+    /// Returns a reference to the error produced by the scalar conversion.
+    pub fn error(&self) -> &E {
+        match self {
+            ComplexError::Re(e) | ComplexError::Im(e) => e,
+        }
+    }
+
+    /// Returns `true` if the real part failed to convert.
+    pub fn is_re(&self) -> bool {
+        matches!(self, ComplexError::Re(_))
+    }
+
+    /// Returns `true` if the imaginary part failed to convert.
+    pub fn is_im(&self) -> bool {
+        matches!(self, ComplexError::Im(_))
+    }
+}
+
+impl<E> Display for ComplexError<E>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ComplexError::Re(_) => f.write_str("real part: ")?,
+            ComplexError::Im(_) => f.write_str("imaginary part: ")?,
+        }
+        // Pass the formatter through, so that `{:#}` reaches the scalar error.
+        Display::fmt(self.error(), f)
+    }
+}
+
+impl<E> std::error::Error for ComplexError<E> where E: Debug + Display {}
+
+impl<E> ConvError for ComplexError<E>
+where
+    E: ConvError,
+{
+    type Payload = E::Payload;
+
+    fn kind(&self) -> GeneralErrorKind {
+        self.error().kind()
+    }
+
+    fn into_inner(self) -> E::Payload {
+        self.into_error().into_inner()
+    }
+
+    fn as_inner(&self) -> &E::Payload {
+        self.error().as_inner()
+    }
+}
+
+impl<E, U> MapPayload<U> for ComplexError<E>
+where
+    E: MapPayload<U>,
+{
+    type Output = ComplexError<E::Output>;
+
+    fn map_payload<F>(self, f: F) -> Self::Output
+    where
+        F: FnOnce(Self::Payload) -> U,
+    {
+        match self {
+            ComplexError::Re(e) => ComplexError::Re(e.map_payload(f)),
+            ComplexError::Im(e) => ComplexError::Im(e.map_payload(f)),
+        }
+    }
+}
+
+/// Implements `ValueFrom` for pairs of component types that have a scalar
+/// `ValueFrom` conversion.
+macro_rules! complex_value {
+    ($($src:ident => $($dst:ident),*;)*) => {
+        $(
+            $(
+                impl crate::ValueFrom<Complex<$src>> for Complex<$dst> {
+                    type Err = ComplexError<<$dst as crate::ValueFrom<$src>>::Err>;
+                    #[inline]
+                    fn value_from(src: Complex<$src>) -> Result<Complex<$dst>, Self::Err> {
+                        Ok(Complex::new(
+                            $dst::value_from(src.re).map_err(ComplexError::Re)?,
+                            $dst::value_from(src.im).map_err(ComplexError::Im)?,
+                        ))
+                    }
+                }
+
+                impl crate::ValueFrom<$src> for Complex<$dst> {
+                    type Err = <$dst as crate::ValueFrom<$src>>::Err;
+                    #[inline]
+                    fn value_from(src: $src) -> Result<Complex<$dst>, Self::Err> {
+                        Ok(Complex::new($dst::value_from(src)?, 0 as $dst))
+                    }
+                }
+            )*
+        )*
+    };
+}
+
+/// Implements `ApproxFrom` for every pair of component types, for whichever
+/// schemes the scalar conversion supports.
+macro_rules! complex_approx {
+    ($($src:ident => $($dst:ident),*;)*) => {
+        $(
+            $(
+                impl<Scheme> crate::ApproxFrom<Complex<$src>, Scheme> for Complex<$dst>
+                where
+                    Scheme: crate::ApproxScheme,
+                    $dst: crate::ApproxFrom<$src, Scheme>,
+                {
+                    type Err = ComplexError<<$dst as crate::ApproxFrom<$src, Scheme>>::Err>;
+                    #[inline]
+                    fn approx_from(src: Complex<$src>) -> Result<Complex<$dst>, Self::Err> {
+                        Ok(Complex::new(
+                            <$dst as crate::ApproxFrom<$src, Scheme>>::approx_from(src.re)
+                                .map_err(ComplexError::Re)?,
+                            <$dst as crate::ApproxFrom<$src, Scheme>>::approx_from(src.im)
+                                .map_err(ComplexError::Im)?,
+                        ))
+                    }
+                }
+            )*
+        )*
+    };
+}
+
+/// Implements `ApproxFrom` from real scalars, including the component type
+/// itself.
+macro_rules! complex_approx_real {
+    ($($src:ident => $($dst:ident),*;)*) => {
+        $(
+            $(
+                impl<Scheme> crate::ApproxFrom<$src, Scheme> for Complex<$dst>
+                where
+                    Scheme: crate::ApproxScheme,
+                    $dst: crate::ApproxFrom<$src, Scheme>,
+                {
+                    type Err = <$dst as crate::ApproxFrom<$src, Scheme>>::Err;
+                    #[inline]
+                    fn approx_from(src: $src) -> Result<Complex<$dst>, Self::Err> {
+                        let re = <$dst as crate::ApproxFrom<$src, Scheme>>::approx_from(src)?;
+                        Ok(Complex::new(re, 0 as $dst))
+                    }
+                }
+            )*
+        )*
+    };
+}
+
+// The identity conversions between complex numbers are covered by the
+// reflexive `ValueFrom` and `ApproxFrom` implementations, so only the real
+// scalar conversions are needed for matching component types.
+item_for_each! {
+    (i8), (i16), (i32), (i64), (isize), (u8), (u16), (u32), (u64), (usize), (f32), (f64) => {
+        ($ty:ident) => {
+            impl crate::ValueFrom<$ty> for Complex<$ty> {
+                type Err = crate::errors::NoError;
+                #[inline]
+                fn value_from(src: $ty) -> Result<Complex<$ty>, Self::Err> {
+                    Ok(Complex::new(src, 0 as $ty))
+                }
+            }
+        };
+    }
+}
+
+complex_value! {
+    i8 => i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    i16 => i8, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    i32 => i8, i16, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    i64 => i8, i16, i32, isize, u8, u16, u32, u64, usize, f32, f64;
+    isize => i8, i16, i32, i64, u8, u16, u32, u64, usize, f32, f64;
+    u8 => i8, i16, i32, i64, isize, u16, u32, u64, usize, f32, f64;
+    u16 => i8, i16, i32, i64, isize, u8, u32, u64, usize, f32, f64;
+    u32 => i8, i16, i32, i64, isize, u8, u16, u64, usize, f32, f64;
+    u64 => i8, i16, i32, i64, isize, u8, u16, u32, usize, f32, f64;
+    usize => i8, i16, i32, i64, isize, u8, u16, u32, u64, f32, f64;
+    f32 => f64;
+}
+
+complex_approx! {
+    i8 => i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    i16 => i8, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    i32 => i8, i16, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    i64 => i8, i16, i32, isize, u8, u16, u32, u64, usize, f32, f64;
+    isize => i8, i16, i32, i64, u8, u16, u32, u64, usize, f32, f64;
+    u8 => i8, i16, i32, i64, isize, u16, u32, u64, usize, f32, f64;
+    u16 => i8, i16, i32, i64, isize, u8, u32, u64, usize, f32, f64;
+    u32 => i8, i16, i32, i64, isize, u8, u16, u64, usize, f32, f64;
+    u64 => i8, i16, i32, i64, isize, u8, u16, u32, usize, f32, f64;
+    usize => i8, i16, i32, i64, isize, u8, u16, u32, u64, f32, f64;
+    f32 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f64;
+    f64 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32;
+}
+
+complex_approx_real! {
+    i8 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    i16 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    i32 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    i64 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    isize => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    u8 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    u16 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    u32 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    u64 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    usize => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    f32 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    f64 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+}
@@ -0,0 +1,90 @@
+//! This module provides helpers for converting values while deserializing
+//! them with `serde`.
+//!
+//! The functions here are intended for use with
+//! `#[serde(deserialize_with = "...")]`. Each one deserializes a value of a
+//! source type, then converts it into the field's type. A failed conversion
+//! is reported as a deserialization error.
+//!
+//! The source type cannot be inferred from the field, so there is one
+//! function for each primitive source type, named after it:
+//!
+//! - `value_from::i64` deserializes an `i64`, then converts it using
+//!   `ValueFrom`.
+//! - `approx_from::f64::<Scheme, _, _>` deserializes an `f64`, then converts
+//!   it using `ApproxFrom` with the approximation scheme `Scheme`.
+//!
+//! This module requires the `serde` feature. It is not named `serde` so that
+//! it does not collide with the `serde` crate for users of `use conv2::*`.
+//!
+//! # Examples
+//!
+//! ```
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Header {
+//!     #[serde(deserialize_with = "conv2::de::value_from::i64")]
+//!     port: u16,
+//!     #[serde(deserialize_with = "conv2::de::approx_from::f64::<conv2::RoundToNearest, _, _>")]
+//!     percent: u8,
+//! }
+//!
+//! let header: Header = serde_json::from_str(r#"{ "port": 8080, "percent": 99.6 }"#).unwrap();
+//! assert_eq!(header.port, 8080);
+//! assert_eq!(header.percent, 100);
+//!
+//! let err = serde_json::from_str::<Header>(r#"{ "port": 70000, "percent": 0 }"#).unwrap_err();
+//! assert!(err.to_string().starts_with("conversion resulted in positive overflow: 70000"));
+//! ```
+
+/// Deserializes a value of the named type, then converts it using
+/// `ValueFrom`.
+pub mod value_from {
+    use crate::ValueFrom;
+    use ::serde::de::{Deserialize, Deserializer, Error};
+
+    item_for_each! {
+        (i8), (i16), (i32), (i64), (isize),
+        (u8), (u16), (u32), (u64), (usize),
+        (f32), (f64) => {
+            ($src:ident) => {
+                #[doc = concat!("Deserializes an `", stringify!($src), "`, then converts it into a `Dst` using `ValueFrom`.")]
+                pub fn $src<'de, Dst, D>(deserializer: D) -> Result<Dst, D::Error>
+                where
+                    Dst: ValueFrom<$src>,
+                    D: Deserializer<'de>,
+                {
+                    let src = <$src as Deserialize>::deserialize(deserializer)?;
+                    Dst::value_from(src).map_err(D::Error::custom)
+                }
+            };
+        }
+    }
+}
+
+/// Deserializes a value of the named type, then converts it using
+/// `ApproxFrom`.
+pub mod approx_from {
+    use crate::{ApproxFrom, ApproxScheme};
+    use ::serde::de::{Deserialize, Deserializer, Error};
+
+    item_for_each! {
+        (i8), (i16), (i32), (i64), (isize),
+        (u8), (u16), (u32), (u64), (usize),
+        (f32), (f64) => {
+            ($src:ident) => {
+                #[doc = concat!("Deserializes an `", stringify!($src), "`, then converts it into a `Dst` using `ApproxFrom` with the approximation scheme `Scheme`.")]
+                pub fn $src<'de, Scheme, Dst, D>(deserializer: D) -> Result<Dst, D::Error>
+                where
+                    Scheme: ApproxScheme,
+                    Dst: ApproxFrom<$src, Scheme>,
+                    D: Deserializer<'de>,
+                {
+                    let src = <$src as Deserialize>::deserialize(deserializer)?;
+                    Dst::approx_from(src).map_err(D::Error::custom)
+                }
+            };
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! This module provides conversions between the builtin numeric types and
+//! the `Decimal` type from the `rust_decimal` crate.
+//!
+//! The following conversions are provided:
+//!
+//! - `ValueFrom` from every primitive integer to `Decimal`, which always
+//!   succeeds.
+//! - `ApproxFrom` from `Decimal` to every primitive integer, which rounds the
+//!   value to an integer and fails with `RangeError` if the result is out of
+//!   range. The `RoundToNearest`, `RoundHalfEven`, `RoundToZero`,
+//!   `RoundToNegInf` and `RoundToPosInf` schemes round as their names
+//!   suggest; `DefaultApprox` truncates, as it does for floats.
+//! - `ValueFrom` from `Decimal` to `f32` and `f64`, which fails with
+//!   `Unrepresentable` unless the decimal value is exactly representable.
+//! - `ApproxFrom` from `f32` and `f64` to `Decimal`, using the `DefaultApprox`
+//!   scheme. The result is the float's value rounded to the 28 fractional
+//!   digits `Decimal` can hold; NaN and values beyond `Decimal`'s range fail
+//!   with `FloatError`.
+//!
+//! This module requires the `rust_decimal` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! # use conv2::*;
+//! use rust_decimal::Decimal;
+//!
+//! let half = Decimal::new(25, 1); // 2.5
+//! assert_eq!(<u8 as ApproxFrom<_, RoundToNearest>>::approx_from(half), Ok(3));
+//! assert_eq!(<u8 as ApproxFrom<_, RoundHalfEven>>::approx_from(half), Ok(2));
+//! assert_eq!(<u8 as ApproxFrom<_, RoundToZero>>::approx_from(-half), Err(RangeError::NegOverflow(-half)));
+//!
+//! assert_eq!(f64::value_from(Decimal::new(125, 3)), Ok(0.125));
+//! assert_eq!(f64::value_from(Decimal::new(1, 1)), Err(Unrepresentable(Decimal::new(1, 1))));
+//!
+//! assert_eq!(Decimal::approx_from(0.125f64), Ok(Decimal::new(125, 3)));
+//! assert!(matches!(Decimal::approx_from(f64::NAN), Err(FloatError::NotANumber(_))));
+//! ```
+
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::errors::{FloatError, NoError, RangeError, Unrepresentable};
+
+/// Decomposes a decimal value into `(mantissa, exponent)` such that the value
+/// is `mantissa * 2^exponent`, with `mantissa` odd (or zero).
+///
+/// Returns `None` if the value is not a binary fraction, such as `0.1`.
+fn binary_parts(src: &Decimal) -> Option<(u128, i32)> {
+    let n = src.mantissa().unsigned_abs();
+    if n == 0 {
+        return Some((0, 0));
+    }
+    // The value is `n / 10^scale`, which is `(n / 5^scale) / 2^scale`. The
+    // scale is at most 28, so `5^scale` fits in a `u128`.
+    let scale = src.scale();
+    let pow5 = 5u128.pow(scale);
+    if n % pow5 != 0 {
+        return None;
+    }
+    let n = n / pow5;
+    let zeros = n.trailing_zeros();
+    Some((n >> zeros, zeros as i32 - scale as i32))
+}
+
+macro_rules! decimal_to_int {
+    ($ity:ident; $scheme:ty => $strategy:expr) => {
+        impl crate::ApproxFrom<Decimal, $scheme> for $ity {
+            type Err = RangeError<Decimal>;
+            #[inline]
+            fn approx_from(src: Decimal) -> Result<$ity, Self::Err> {
+                let approx = src.round_dp_with_strategy(0, $strategy);
+                $ity::try_from(approx).map_err(|_| {
+                    if src.is_sign_negative() {
+                        RangeError::NegOverflow(src)
+                    } else {
+                        RangeError::PosOverflow(src)
+                    }
+                })
+            }
+        }
+    };
+}
+
+item_for_each! {
+    (i8), (i16), (i32), (i64), (isize), (u8), (u16), (u32), (u64), (usize) => {
+        ($ity:ident) => {
+            impl crate::ValueFrom<$ity> for Decimal {
+                type Err = NoError;
+                #[inline]
+                fn value_from(src: $ity) -> Result<Decimal, Self::Err> {
+                    Ok(Decimal::from(src))
+                }
+            }
+
+            decimal_to_int! { $ity; crate::DefaultApprox => RoundingStrategy::ToZero }
+            decimal_to_int! { $ity; crate::RoundToNearest => RoundingStrategy::MidpointAwayFromZero }
+            decimal_to_int! { $ity; crate::RoundHalfEven => RoundingStrategy::MidpointNearestEven }
+            decimal_to_int! { $ity; crate::RoundToZero => RoundingStrategy::ToZero }
+            decimal_to_int! { $ity; crate::RoundToNegInf => RoundingStrategy::ToNegativeInfinity }
+            decimal_to_int! { $ity; crate::RoundToPosInf => RoundingStrategy::ToPositiveInfinity }
+        };
+    }
+}
+
+item_for_each! {
+    (f32, 24, from_f32_retain), (f64, 53, from_f64_retain) => {
+        ($fty:ident, $mant_bits:expr, $from_float:ident) => {
+            impl crate::ValueFrom<Decimal> for $fty {
+                type Err = Unrepresentable<Decimal>;
+                #[inline]
+                fn value_from(src: Decimal) -> Result<$fty, Self::Err> {
+                    match binary_parts(&src) {
+                        Some((mantissa, exp)) if mantissa >> $mant_bits == 0 => {
+                            let dst = mantissa as $fty * (2.0 as $fty).powi(exp);
+                            Ok(if src.is_sign_negative() { -dst } else { dst })
+                        }
+                        _ => Err(Unrepresentable(src)),
+                    }
+                }
+            }
+
+            impl crate::ApproxFrom<$fty, crate::DefaultApprox> for Decimal {
+                type Err = FloatError<$fty>;
+                #[inline]
+                fn approx_from(src: $fty) -> Result<Decimal, Self::Err> {
+                    if src.is_nan() {
+                        return Err(FloatError::NotANumber(src));
+                    }
+                    Decimal::$from_float(src).ok_or(if src < 0.0 {
+                        FloatError::NegOverflow(src)
+                    } else {
+                        FloatError::PosOverflow(src)
+                    })
+                }
+            }
+        };
+    }
+}
@@ -3,29 +3,81 @@
 //!
 //! In addition, it also defines some extension traits to make working with
 //! failable conversions more ergonomic (see the `Unwrap*` traits).
+//!
+//! Errors that carry a payload include it in their `Display` output, using
+//! the payload's `Debug` formatting, so a failed conversion reads as *e.g.*
+//! "conversion resulted in positive overflow: 256.0". The alternate form
+//! (`{:#}`) also names the variant, as in "PosOverflow: conversion resulted
+//! in positive overflow: 256.0".
+//!
+//! Since only `Debug` is needed, every error type implements
+//! `std::error::Error` whenever its payload implements `Debug`.
 
-use crate::misc::{InvalidSentinel, Saturated, SignedInfinity};
+use crate::misc::{ConvRange, InvalidSentinel, Saturated, SignedInfinity};
 use core::fmt::{self, Debug, Display};
+use std::any::type_name;
+use std::io;
+
+const NEG_OVERFLOW: &str = "conversion resulted in negative overflow";
+const POS_OVERFLOW: &str = "conversion resulted in positive overflow";
+const UNREPRESENTABLE: &str = "could not convert unrepresentable value";
+const NOT_A_NUMBER: &str = "conversion target does not support not-a-number";
+
+/// Writes an error message, followed by the payload (if there is one).
+///
+/// The alternate form (`{:#}`) prefixes the message with the variant name.
+/// The payload is written with its `Debug` formatting, so that errors do not
+/// need a `Display` payload.
+pub(crate) fn write_error(
+    f: &mut fmt::Formatter,
+    variant: &str,
+    msg: &str,
+    payload: Option<&dyn Debug>,
+) -> fmt::Result {
+    if f.alternate() {
+        write!(f, "{}: ", variant)?;
+    }
+    f.write_str(msg)?;
+    if let Some(payload) = payload {
+        write!(f, ": {:?}", payload)?;
+    }
+    Ok(())
+}
 
 /// A general error enumeration that subsumes all other conversion errors.
 ///
 /// This exists primarily as a "catch-all" for reliably unifying various
 /// different kinds of conversion errors.
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, thiserror::Error)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GeneralError<T> {
     /// Input was too negative for the target type.
-    #[error("conversion resulted in negative overflow")]
     NegOverflow(T),
 
     /// Input was too positive for the target type.
-    #[error("conversion resulted in positive overflow")]
     PosOverflow(T),
 
     /// Input was not representable in the target type.
-    #[error("could not convert unrepresentable value")]
     Unrepresentable(T),
 }
 
+impl<T> Display for GeneralError<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GeneralError::NegOverflow(v) => write_error(f, "NegOverflow", NEG_OVERFLOW, Some(v)),
+            GeneralError::PosOverflow(v) => write_error(f, "PosOverflow", POS_OVERFLOW, Some(v)),
+            GeneralError::Unrepresentable(v) => {
+                write_error(f, "Unrepresentable", UNREPRESENTABLE, Some(v))
+            }
+        }
+    }
+}
+
+impl<T> std::error::Error for GeneralError<T> where T: Debug {}
+
 impl<T> GeneralError<T> {
     /// Returns the value stored in this error.
     pub fn into_inner(self) -> T {
@@ -87,21 +139,39 @@ impl<T> From<FloatError<T>> for GeneralError<T> {
 ///
 /// This exists primarily as a "catch-all" for reliably unifying various
 /// different kinds of conversion errors, and between different input types.
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, thiserror::Error)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GeneralErrorKind {
     /// Input was too negative for the target type.
-    #[error("conversion resulted in negative overflow")]
     NegOverflow,
 
     /// Input was too positive for the target type.
-    #[error("conversion resulted in positive overflow")]
     PosOverflow,
 
     /// Input was not representable in the target type.
-    #[error("could not convert unrepresentable value")]
     Unrepresentable,
 }
 
+impl GeneralErrorKind {
+    /// Returns the variant name and the message used by `Display`.
+    fn describe(self) -> (&'static str, &'static str) {
+        match self {
+            GeneralErrorKind::NegOverflow => ("NegOverflow", NEG_OVERFLOW),
+            GeneralErrorKind::PosOverflow => ("PosOverflow", POS_OVERFLOW),
+            GeneralErrorKind::Unrepresentable => ("Unrepresentable", UNREPRESENTABLE),
+        }
+    }
+}
+
+impl Display for GeneralErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (variant, msg) = self.describe();
+        write_error(f, variant, msg, None)
+    }
+}
+
+impl std::error::Error for GeneralErrorKind {}
+
 impl From<NoError> for GeneralErrorKind {
     fn from(_: NoError) -> Self {
         unreachable!();
@@ -170,6 +240,7 @@ impl<T> From<FloatError<T>> for GeneralErrorKind {
 /// `Err` case from a `Result<_, NoError>`, without using `Result::unwrap` (which is
 /// typically viewed as a "code smell").
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NoError {}
 
 impl Display for NoError {
@@ -181,10 +252,21 @@ impl Display for NoError {
 impl std::error::Error for NoError {}
 
 /// Indicates that the conversion failed because the value was not representable.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, thiserror::Error)]
-#[error("could not convert unrepresentable value")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Unrepresentable<T>(pub T);
 
+impl<T> Display for Unrepresentable<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_error(f, "Unrepresentable", UNREPRESENTABLE, Some(&self.0))
+    }
+}
+
+impl<T> std::error::Error for Unrepresentable<T> where T: Debug {}
+
 impl<T> From<NoError> for Unrepresentable<T> {
     fn from(_: NoError) -> Self {
         unreachable!();
@@ -192,10 +274,21 @@ impl<T> From<NoError> for Unrepresentable<T> {
 }
 
 /// Indicates that the conversion failed due to a negative overflow.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, thiserror::Error)]
-#[error("conversion resulted in negative overflow")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NegOverflow<T>(pub T);
 
+impl<T> Display for NegOverflow<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_error(f, "NegOverflow", NEG_OVERFLOW, Some(&self.0))
+    }
+}
+
+impl<T> std::error::Error for NegOverflow<T> where T: Debug {}
+
 impl<T> From<NoError> for NegOverflow<T> {
     fn from(_: NoError) -> Self {
         unreachable!();
@@ -203,10 +296,21 @@ impl<T> From<NoError> for NegOverflow<T> {
 }
 
 /// Indicates that the conversion failed due to a positive overflow.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, thiserror::Error)]
-#[error("conversion resulted in positive overflow")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PosOverflow<T>(pub T);
 
+impl<T> Display for PosOverflow<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_error(f, "PosOverflow", POS_OVERFLOW, Some(&self.0))
+    }
+}
+
+impl<T> std::error::Error for PosOverflow<T> where T: Debug {}
+
 impl<T> From<NoError> for PosOverflow<T> {
     fn from(_: NoError) -> Self {
         unreachable!();
@@ -214,21 +318,34 @@ impl<T> From<NoError> for PosOverflow<T> {
 }
 
 /// Indicates that a conversion from a floating point type failed.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, thiserror::Error)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FloatError<T> {
     /// Input was too negative for the target type.
-    #[error("conversion resulted in negative overflow")]
     NegOverflow(T),
 
     /// Input was too positive for the target type.
-    #[error("conversion resulted in positive overflow")]
     PosOverflow(T),
 
     /// Input was not-a-number, which the target type could not represent.
-    #[error("conversion target does not support not-a-number")]
     NotANumber(T),
 }
 
+impl<T> Display for FloatError<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FloatError::NegOverflow(v) => write_error(f, "NegOverflow", NEG_OVERFLOW, Some(v)),
+            FloatError::PosOverflow(v) => write_error(f, "PosOverflow", POS_OVERFLOW, Some(v)),
+            FloatError::NotANumber(v) => write_error(f, "NotANumber", NOT_A_NUMBER, Some(v)),
+        }
+    }
+}
+
+impl<T> std::error::Error for FloatError<T> where T: Debug {}
+
 impl<T> FloatError<T> {
     /// Returns the value stored in this error.
     pub fn into_inner(self) -> T {
@@ -268,17 +385,30 @@ impl<T> From<RangeError<T>> for FloatError<T> {
 }
 
 /// Indicates that a conversion failed due to a range error.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, thiserror::Error)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RangeError<T> {
     /// Input was too negative for the target type.
-    #[error("conversion resulted in negative overflow")]
     NegOverflow(T),
 
     /// Input was too positive the target type.
-    #[error("conversion resulted in positive overflow")]
     PosOverflow(T),
 }
 
+impl<T> Display for RangeError<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RangeError::NegOverflow(v) => write_error(f, "NegOverflow", NEG_OVERFLOW, Some(v)),
+            RangeError::PosOverflow(v) => write_error(f, "PosOverflow", POS_OVERFLOW, Some(v)),
+        }
+    }
+}
+
+impl<T> std::error::Error for RangeError<T> where T: Debug {}
+
 impl<T> From<NoError> for RangeError<T> {
     fn from(_: NoError) -> Self {
         unreachable!();
@@ -303,17 +433,27 @@ impl<T> From<PosOverflow<T>> for RangeError<T> {
 /// which caused the error. It exists to help unify some utility methods
 /// and should not generally be used directly, unless you are targeting the
 /// `Unwrap*` traits.
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, thiserror::Error)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RangeErrorKind {
     /// Input was too negative for the target type.
-    #[error("conversion resulted in negative overflow")]
     NegOverflow,
 
     /// Input was too positive the target type.
-    #[error("conversion resulted in positive overflow")]
     PosOverflow,
 }
 
+impl Display for RangeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RangeErrorKind::NegOverflow => write_error(f, "NegOverflow", NEG_OVERFLOW, None),
+            RangeErrorKind::PosOverflow => write_error(f, "PosOverflow", POS_OVERFLOW, None),
+        }
+    }
+}
+
+impl std::error::Error for RangeErrorKind {}
+
 impl From<NoError> for RangeErrorKind {
     fn from(_: NoError) -> Self {
         unreachable!();
@@ -341,6 +481,587 @@ impl<T> From<RangeError<T>> for RangeErrorKind {
     }
 }
 
+/// A conversion error with the payload type erased.
+///
+/// This keeps the kind of failure, the value that failed to convert
+/// (formatted with `Debug`), and the names of the source and destination
+/// types. Unlike the other error types in this module, it is `Send + Sync +
+/// 'static` regardless of the payload, which makes it suitable for boxing or
+/// wrapping in an [`io::Error`].
+///
+/// # Examples
+///
+/// ```
+/// # use conv2::*;
+/// use conv2::errors::ConversionError;
+///
+/// let err = u8::value_from(300i32).map_err(ConversionError::new::<u8, _, _>).unwrap_err();
+/// assert_eq!(err.kind(), GeneralErrorKind::PosOverflow);
+/// assert_eq!(err.value(), "300");
+/// assert_eq!(err.src_type(), "i32");
+/// assert_eq!(err.dst_type(), "u8");
+/// assert_eq!(err.to_string(), "conversion resulted in positive overflow: 300 (i32 to u8)");
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ConversionError(Box<ConversionErrorInner>);
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct ConversionErrorInner {
+    kind: GeneralErrorKind,
+    value: String,
+    src: &'static str,
+    dst: &'static str,
+}
+
+impl ConversionError {
+    /// Creates an error from a failed conversion of a `T` into a `Dst`.
+    ///
+    /// The source type is taken from the error, but the destination type
+    /// cannot be, so it has to be given: `ConversionError::new::<u8, _, _>`.
+    pub fn new<Dst, T, E>(err: E) -> Self
+    where
+        E: Into<GeneralError<T>>,
+        T: Debug,
+    {
+        let (kind, value) = split_general(err.into());
+        ConversionError(Box::new(ConversionErrorInner {
+            kind,
+            value: format!("{:?}", value),
+            src: type_name::<T>(),
+            dst: type_name::<Dst>(),
+        }))
+    }
+
+    /// Returns the kind of failure.
+    pub fn kind(&self) -> GeneralErrorKind {
+        self.0.kind
+    }
+
+    /// Returns the value that failed to convert, formatted with `Debug`.
+    pub fn value(&self) -> &str {
+        &self.0.value
+    }
+
+    /// Returns the name of the source type.
+    pub fn src_type(&self) -> &'static str {
+        self.0.src
+    }
+
+    /// Returns the name of the destination type.
+    pub fn dst_type(&self) -> &'static str {
+        self.0.dst
+    }
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (variant, msg) = self.0.kind.describe();
+        // The value is already formatted, so it is written as is.
+        write_error(f, variant, msg, Some(&format_args!("{}", self.0.value)))?;
+        write!(f, " ({} to {})", self.0.src, self.0.dst)
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+impl From<ConversionError> for io::Error {
+    fn from(e: ConversionError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+/// A conversion error that records the source and destination types, the
+/// value that failed to convert, and the range of the destination.
+///
+/// This is produced by the [`WithContext`] extension trait, and is intended
+/// for errors that end up in logs, where a bare "conversion resulted in
+/// positive overflow" is not very helpful.
+///
+/// # Examples
+///
+/// ```
+/// # use conv2::*;
+/// let err = 300i32.value_as::<u8>().with_context().unwrap_err();
+/// assert_eq!(err.kind, GeneralErrorKind::PosOverflow);
+/// assert_eq!(err.to_string(), "value 300 (i32) out of range for u8 [0, 255]");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContextError<Src, Dst> {
+    /// The kind of failure.
+    pub kind: GeneralErrorKind,
+
+    /// The value that failed to convert.
+    pub value: Src,
+
+    /// The smallest value the conversion can produce.
+    pub min: Dst,
+
+    /// The largest value the conversion can produce.
+    pub max: Dst,
+}
+
+impl<Src, Dst> ContextError<Src, Dst> {
+    /// Creates an error from a failed conversion of a `Src` into a `Dst`.
+    pub fn new<E>(err: E) -> Self
+    where
+        E: Into<GeneralError<Src>>,
+        Dst: ConvRange<Src>,
+    {
+        let (kind, value) = split_general(err.into());
+        ContextError {
+            kind,
+            value,
+            min: Dst::range_min(),
+            max: Dst::range_max(),
+        }
+    }
+
+    /// Returns the value stored in this error.
+    pub fn into_inner(self) -> Src {
+        self.value
+    }
+
+    /// Returns the name of the source type.
+    pub fn src_type(&self) -> &'static str {
+        type_name::<Src>()
+    }
+
+    /// Returns the name of the destination type.
+    pub fn dst_type(&self) -> &'static str {
+        type_name::<Dst>()
+    }
+}
+
+impl<Src, Dst> Display for ContextError<Src, Dst>
+where
+    Src: Display,
+    Dst: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            GeneralErrorKind::NegOverflow | GeneralErrorKind::PosOverflow => write!(
+                f,
+                "value {} ({}) out of range for {} [{}, {}]",
+                self.value,
+                self.src_type(),
+                self.dst_type(),
+                self.min,
+                self.max
+            ),
+            GeneralErrorKind::Unrepresentable => write!(
+                f,
+                "value {} ({}) not representable in {}",
+                self.value,
+                self.src_type(),
+                self.dst_type()
+            ),
+        }
+    }
+}
+
+impl<Src, Dst> std::error::Error for ContextError<Src, Dst>
+where
+    Src: Debug + Display,
+    Dst: Debug + Display,
+{
+}
+
+// Conversions into `io::Error` all use `ErrorKind::InvalidData`. Errors that
+// carry a payload include it in the message.
+
+impl<T> From<GeneralError<T>> for io::Error
+where
+    T: Debug,
+{
+    fn from(e: GeneralError<T>) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+    }
+}
+
+impl<T> From<FloatError<T>> for io::Error
+where
+    T: Debug,
+{
+    fn from(e: FloatError<T>) -> Self {
+        GeneralError::from(e).into()
+    }
+}
+
+impl<T> From<RangeError<T>> for io::Error
+where
+    T: Debug,
+{
+    fn from(e: RangeError<T>) -> Self {
+        GeneralError::from(e).into()
+    }
+}
+
+impl<T> From<NegOverflow<T>> for io::Error
+where
+    T: Debug,
+{
+    fn from(e: NegOverflow<T>) -> Self {
+        GeneralError::from(e).into()
+    }
+}
+
+impl<T> From<PosOverflow<T>> for io::Error
+where
+    T: Debug,
+{
+    fn from(e: PosOverflow<T>) -> Self {
+        GeneralError::from(e).into()
+    }
+}
+
+impl<T> From<Unrepresentable<T>> for io::Error
+where
+    T: Debug,
+{
+    fn from(e: Unrepresentable<T>) -> Self {
+        GeneralError::from(e).into()
+    }
+}
+
+/// Splits a `GeneralError` into its kind and payload.
+fn split_general<T>(e: GeneralError<T>) -> (GeneralErrorKind, T) {
+    match e {
+        GeneralError::NegOverflow(v) => (GeneralErrorKind::NegOverflow, v),
+        GeneralError::PosOverflow(v) => (GeneralErrorKind::PosOverflow, v),
+        GeneralError::Unrepresentable(v) => (GeneralErrorKind::Unrepresentable, v),
+    }
+}
+
+impl From<GeneralErrorKind> for io::Error {
+    fn from(e: GeneralErrorKind) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+impl From<RangeErrorKind> for io::Error {
+    fn from(e: RangeErrorKind) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+impl From<NoError> for io::Error {
+    fn from(e: NoError) -> Self {
+        match e {}
+    }
+}
+
+/// A common interface to the error types defined in this module.
+///
+/// This allows generic code to inspect a failed conversion without knowing
+/// which specific error type it produced. Error types without a payload (such
+/// as `GeneralErrorKind`) use `()` as their payload.
+///
+/// See also [`MapPayload`], which transforms the payload of an error.
+///
+/// # Examples
+///
+/// ```
+/// # use conv2::*;
+/// fn describe<E: ConvError>(e: &E) -> String
+/// where
+///     E::Payload: std::fmt::Debug,
+/// {
+///     format!("{:?} {:?}", e.kind(), e.as_inner())
+/// }
+///
+/// assert_eq!(describe(&RangeError::PosOverflow(300i32)), "PosOverflow 300");
+/// assert_eq!(describe(&FloatError::NotANumber(f32::NAN)), "Unrepresentable NaN");
+/// ```
+pub trait ConvError: Sized {
+    /// The type of the value carried by this error.
+    type Payload;
+
+    /// Returns the kind of failure.
+    fn kind(&self) -> GeneralErrorKind;
+
+    /// Returns the value stored in this error.
+    fn into_inner(self) -> Self::Payload;
+
+    /// Returns a reference to the value stored in this error.
+    fn as_inner(&self) -> &Self::Payload;
+}
+
+/// Transforms the payload of an error, keeping the shape of the error.
+///
+/// This is separate from [`ConvError`] because the output type depends on the
+/// new payload type `U`.
+///
+/// # Examples
+///
+/// ```
+/// # use conv2::*;
+/// let e = RangeError::NegOverflow(-1i32).map_payload(|v| v.to_string());
+/// assert_eq!(e, RangeError::NegOverflow(String::from("-1")));
+/// ```
+pub trait MapPayload<U>: ConvError {
+    /// The error type with a payload of type `U`.
+    type Output: ConvError;
+
+    /// Applies `f` to the payload, producing the same kind of error.
+    fn map_payload<F>(self, f: F) -> Self::Output
+    where
+        F: FnOnce(Self::Payload) -> U;
+}
+
+impl<T> ConvError for GeneralError<T> {
+    type Payload = T;
+
+    fn kind(&self) -> GeneralErrorKind {
+        match self {
+            GeneralError::NegOverflow(..) => GeneralErrorKind::NegOverflow,
+            GeneralError::PosOverflow(..) => GeneralErrorKind::PosOverflow,
+            GeneralError::Unrepresentable(..) => GeneralErrorKind::Unrepresentable,
+        }
+    }
+
+    fn into_inner(self) -> T {
+        GeneralError::into_inner(self)
+    }
+
+    fn as_inner(&self) -> &T {
+        match self {
+            GeneralError::NegOverflow(v)
+            | GeneralError::PosOverflow(v)
+            | GeneralError::Unrepresentable(v) => v,
+        }
+    }
+}
+
+impl<T, U> MapPayload<U> for GeneralError<T> {
+    type Output = GeneralError<U>;
+
+    fn map_payload<F>(self, f: F) -> GeneralError<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            GeneralError::NegOverflow(v) => GeneralError::NegOverflow(f(v)),
+            GeneralError::PosOverflow(v) => GeneralError::PosOverflow(f(v)),
+            GeneralError::Unrepresentable(v) => GeneralError::Unrepresentable(f(v)),
+        }
+    }
+}
+
+impl<T> ConvError for FloatError<T> {
+    type Payload = T;
+
+    fn kind(&self) -> GeneralErrorKind {
+        match self {
+            FloatError::NegOverflow(..) => GeneralErrorKind::NegOverflow,
+            FloatError::PosOverflow(..) => GeneralErrorKind::PosOverflow,
+            FloatError::NotANumber(..) => GeneralErrorKind::Unrepresentable,
+        }
+    }
+
+    fn into_inner(self) -> T {
+        FloatError::into_inner(self)
+    }
+
+    fn as_inner(&self) -> &T {
+        match self {
+            FloatError::NegOverflow(v) | FloatError::PosOverflow(v) | FloatError::NotANumber(v) => {
+                v
+            }
+        }
+    }
+}
+
+impl<T, U> MapPayload<U> for FloatError<T> {
+    type Output = FloatError<U>;
+
+    fn map_payload<F>(self, f: F) -> FloatError<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            FloatError::NegOverflow(v) => FloatError::NegOverflow(f(v)),
+            FloatError::PosOverflow(v) => FloatError::PosOverflow(f(v)),
+            FloatError::NotANumber(v) => FloatError::NotANumber(f(v)),
+        }
+    }
+}
+
+impl<T> ConvError for RangeError<T> {
+    type Payload = T;
+
+    fn kind(&self) -> GeneralErrorKind {
+        match self {
+            RangeError::NegOverflow(..) => GeneralErrorKind::NegOverflow,
+            RangeError::PosOverflow(..) => GeneralErrorKind::PosOverflow,
+        }
+    }
+
+    fn into_inner(self) -> T {
+        match self {
+            RangeError::NegOverflow(v) | RangeError::PosOverflow(v) => v,
+        }
+    }
+
+    fn as_inner(&self) -> &T {
+        match self {
+            RangeError::NegOverflow(v) | RangeError::PosOverflow(v) => v,
+        }
+    }
+}
+
+impl<T, U> MapPayload<U> for RangeError<T> {
+    type Output = RangeError<U>;
+
+    fn map_payload<F>(self, f: F) -> RangeError<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            RangeError::NegOverflow(v) => RangeError::NegOverflow(f(v)),
+            RangeError::PosOverflow(v) => RangeError::PosOverflow(f(v)),
+        }
+    }
+}
+
+item_for_each! {
+    (NegOverflow), (PosOverflow), (Unrepresentable) => {
+        ($ety:ident) => {
+            impl<T> ConvError for $ety<T> {
+                type Payload = T;
+
+                fn kind(&self) -> GeneralErrorKind {
+                    GeneralErrorKind::$ety
+                }
+
+                fn into_inner(self) -> T {
+                    self.0
+                }
+
+                fn as_inner(&self) -> &T {
+                    &self.0
+                }
+            }
+
+            impl<T, U> MapPayload<U> for $ety<T> {
+                type Output = $ety<U>;
+
+                fn map_payload<F>(self, f: F) -> $ety<U>
+                where
+                    F: FnOnce(T) -> U,
+                {
+                    $ety(f(self.0))
+                }
+            }
+        };
+    }
+}
+
+impl ConvError for NoError {
+    type Payload = NoError;
+
+    fn kind(&self) -> GeneralErrorKind {
+        match *self {}
+    }
+
+    fn into_inner(self) -> NoError {
+        self
+    }
+
+    fn as_inner(&self) -> &NoError {
+        self
+    }
+}
+
+impl<U> MapPayload<U> for NoError {
+    type Output = NoError;
+
+    fn map_payload<F>(self, _: F) -> NoError
+    where
+        F: FnOnce(NoError) -> U,
+    {
+        self
+    }
+}
+
+impl ConvError for GeneralErrorKind {
+    type Payload = ();
+
+    fn kind(&self) -> GeneralErrorKind {
+        *self
+    }
+
+    fn into_inner(self) {}
+
+    fn as_inner(&self) -> &() {
+        &()
+    }
+}
+
+impl ConvError for RangeErrorKind {
+    type Payload = ();
+
+    fn kind(&self) -> GeneralErrorKind {
+        (*self).into()
+    }
+
+    fn into_inner(self) {}
+
+    fn as_inner(&self) -> &() {
+        &()
+    }
+}
+
+impl ConvError for ConversionError {
+    type Payload = String;
+
+    fn kind(&self) -> GeneralErrorKind {
+        self.0.kind
+    }
+
+    fn into_inner(self) -> String {
+        self.0.value
+    }
+
+    fn as_inner(&self) -> &String {
+        &self.0.value
+    }
+}
+
+impl<Src, Dst> ConvError for ContextError<Src, Dst> {
+    type Payload = Src;
+
+    fn kind(&self) -> GeneralErrorKind {
+        self.kind
+    }
+
+    fn into_inner(self) -> Src {
+        self.value
+    }
+
+    fn as_inner(&self) -> &Src {
+        &self.value
+    }
+}
+
+impl<Src, Dst, U> MapPayload<U> for ContextError<Src, Dst> {
+    type Output = ContextError<U, Dst>;
+
+    fn map_payload<F>(self, f: F) -> ContextError<U, Dst>
+    where
+        F: FnOnce(Src) -> U,
+    {
+        ContextError {
+            kind: self.kind,
+            value: f(self.value),
+            min: self.min,
+            max: self.max,
+        }
+    }
+}
+
 /// Saturates a `Result`.
 pub trait Saturate {
     /// The result of saturating.
@@ -405,6 +1126,41 @@ where
     }
 }
 
+/// Attaches type and range information to a failed conversion.
+pub trait WithContext {
+    /// The result of attaching context.
+    type Output;
+
+    /// Replaces a conversion error with a [`ContextError`], which records the
+    /// source and destination types and the range of the destination.
+    fn with_context(self) -> Self::Output;
+}
+
+item_for_each! {
+    (NegOverflow), (PosOverflow), (Unrepresentable), (RangeError), (FloatError), (GeneralError) => {
+        ($ety:ident) => {
+            impl<T, U> WithContext for Result<T, $ety<U>>
+            where
+                T: ConvRange<U>,
+            {
+                type Output = Result<T, ContextError<U, T>>;
+
+                fn with_context(self) -> Self::Output {
+                    self.map_err(ContextError::new)
+                }
+            }
+        };
+    }
+}
+
+impl<T> WithContext for Result<T, NoError> {
+    type Output = Result<T, NoError>;
+
+    fn with_context(self) -> Self::Output {
+        self
+    }
+}
+
 /// Safely unwrap a `Result` that cannot contain an error.
 pub trait UnwrapOk<T> {
     /// Unwraps a `Result` without possibility of failing.
@@ -0,0 +1,324 @@
+//! This module provides conversions between the builtin numeric types and
+//! the fixed-point types from the `fixed` crate (`FixedI8` through
+//! `FixedI128` and `FixedU8` through `FixedU128`, along with aliases such as
+//! `I16F16`).
+//!
+//! The following conversions are provided:
+//!
+//! - `ValueFrom` from every primitive integer to every fixed-point type,
+//!   which fails with `RangeError` if the value does not fit.
+//! - `ValueFrom` from every fixed-point type to every primitive integer,
+//!   which fails with `Unrepresentable` if the value has a fractional part,
+//!   and with `NegOverflow` or `PosOverflow` if it does not fit.
+//! - `ValueFrom` from every fixed-point type to `f32` and `f64`, which fails
+//!   with `Unrepresentable` unless the value is exactly representable.
+//! - `ValueFrom` from `f32` and `f64` to every fixed-point type, which fails
+//!   with `Unrepresentable` if the value is NaN or needs more fractional bits
+//!   than the destination has, and with `NegOverflow` or `PosOverflow` if it
+//!   does not fit.
+//! - `ApproxFrom` from every fixed-point type to `f32` and `f64`, using the
+//!   `DefaultApprox` scheme, which rounds to nearest.
+//! - `ApproxFrom` from every fixed-point type to every primitive integer,
+//!   and from `f32` and `f64` to every fixed-point type. These support the
+//!   `DefaultApprox`, `RoundToNearest`, `RoundHalfEven`, `RoundToZero`,
+//!   `RoundToNegInf`, `RoundToPosInf` and `Wrapping` schemes.
+//!
+//! When rounding to an integer, `DefaultApprox` truncates, as it does for
+//! floats. When rounding a float to a fixed-point type, `DefaultApprox`
+//! rounds to nearest with ties to even, as the `fixed` crate's own
+//! conversions do. `Wrapping` discards any bits below the destination's
+//! precision (rounding toward negative infinity) and then wraps the result
+//! into the destination's range, as the `fixed` crate's `wrapping_to_num`
+//! does.
+//!
+//! Values that are out of range after rounding fail with `RangeError`
+//! (fixed-point sources) or `FloatError` (float sources). NaN always fails
+//! with `FloatError::NotANumber`, and infinities always fail, even when using
+//! `Wrapping`.
+//!
+//! This module requires the `fixed` feature. It is not named `fixed` so that
+//! it does not collide with the `fixed` crate for users of `use conv2::*`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use conv2::*;
+//! use fixed::types::{I16F16, U8F8};
+//!
+//! let x = I16F16::from_num(-2.5);
+//! assert_eq!(<i32 as ApproxFrom<_, RoundToNearest>>::approx_from(x), Ok(-3));
+//! assert_eq!(<i32 as ApproxFrom<_, RoundHalfEven>>::approx_from(x), Ok(-2));
+//! assert_eq!(<i32 as ApproxFrom<_, RoundToPosInf>>::approx_from(x), Ok(-2));
+//! assert_eq!(<u8 as ApproxFrom<_, RoundToZero>>::approx_from(x), Err(RangeError::NegOverflow(x)));
+//! assert_eq!(<u8 as ApproxFrom<_, Wrapping>>::approx_from(x), Ok(253));
+//!
+//! assert_eq!(I16F16::value_from(1000i32), Ok(I16F16::from_num(1000)));
+//! assert_eq!(U8F8::value_from(256i32), Err(RangeError::PosOverflow(256)));
+//! assert_eq!(f64::value_from(x), Ok(-2.5));
+//! assert_eq!(i32::value_from(I16F16::from_num(-2)), Ok(-2));
+//! assert_eq!(i32::value_from(x), Err(GeneralError::Unrepresentable(x)));
+//! assert_eq!(U8F8::value_from(0.75f32), Ok(U8F8::from_num(0.75)));
+//! assert_eq!(U8F8::value_from(0.1f32), Err(GeneralError::Unrepresentable(0.1)));
+//!
+//! assert_eq!(<U8F8 as ApproxFrom<_, RoundToNegInf>>::approx_from(0.1f32), Ok(U8F8::from_bits(25)));
+//! assert_eq!(<U8F8 as ApproxFrom<_, RoundToPosInf>>::approx_from(0.1f32), Ok(U8F8::from_bits(26)));
+//! assert_eq!(<U8F8 as ApproxFrom<_, RoundToZero>>::approx_from(256.0f32), Err(FloatError::PosOverflow(256.0)));
+//! ```
+
+use fixed::types::extra::{LeEqU128, LeEqU16, LeEqU32, LeEqU64, LeEqU8};
+use fixed::{
+    FixedI128, FixedI16, FixedI32, FixedI64, FixedI8, FixedU128, FixedU16, FixedU32, FixedU64,
+    FixedU8,
+};
+
+use crate::errors::{FloatError, GeneralError, NoError, RangeError, Unrepresentable};
+
+/// How to round a fixed-point or float value to an integer.
+#[derive(Copy, Clone)]
+enum Rounding {
+    /// Round to nearest, ties away from zero.
+    Nearest,
+    /// Round to nearest, ties to even.
+    NearestEven,
+    Zero,
+    NegInf,
+    PosInf,
+}
+
+/// Decides whether to round up from the integer part of a fixed-point value.
+///
+/// `rem` holds the `frac` fractional bits of the value, `negative` is its
+/// sign, and `odd` is whether the integer part (rounded toward negative
+/// infinity) is odd.
+fn round_up(rem: u128, frac: u32, negative: bool, odd: bool, rounding: Rounding) -> bool {
+    if rem == 0 {
+        return false;
+    }
+    let half = 1u128 << (frac - 1);
+    match rounding {
+        Rounding::Nearest => rem > half || (rem == half && !negative),
+        Rounding::NearestEven => rem > half || (rem == half && odd),
+        Rounding::Zero => negative,
+        Rounding::NegInf => false,
+        Rounding::PosInf => true,
+    }
+}
+
+/// Scales a float by `2^frac` and rounds it to an integer.
+///
+/// The scaling is done in `f64`, where it is exact for every `f32` and for
+/// every `f64` that is not far outside the range of a 128-bit fixed-point
+/// type.
+fn scale_round(src: f64, frac: u32, rounding: Rounding) -> f64 {
+    let scaled = src * 2f64.powi(frac as i32);
+    match rounding {
+        Rounding::Nearest => scaled.round(),
+        Rounding::NearestEven => {
+            // `round` breaks ties away from zero; move them to the even
+            // neighbour instead.
+            let rounded = scaled.round();
+            if (scaled - scaled.trunc()).abs() == 0.5 && rounded % 2.0 != 0.0 {
+                rounded - scaled.signum()
+            } else {
+                rounded
+            }
+        }
+        Rounding::Zero => scaled.trunc(),
+        Rounding::NegInf => scaled.floor(),
+        Rounding::PosInf => scaled.ceil(),
+    }
+}
+
+macro_rules! fixed_conv {
+    ($($fixed:ident<$leq:ident>: $bits:ident, $wide:ident;)*) => {
+        $(
+            fixed_conv! {
+                @int $fixed<$leq>: $bits, $wide;
+                i8, i16, i32, i64, isize, u8, u16, u32, u64, usize
+            }
+            fixed_conv! { @float $fixed<$leq>: $bits, $wide; f32, f64 }
+        )*
+    };
+
+    (@int $fixed:ident<$leq:ident>: $bits:ident, $wide:ident; $($ity:ident),*) => {
+        $(
+            impl<Frac: $leq> crate::ValueFrom<$ity> for $fixed<Frac> {
+                type Err = RangeError<$ity>;
+                #[inline]
+                #[allow(unused_comparisons)]
+                fn value_from(src: $ity) -> Result<$fixed<Frac>, Self::Err> {
+                    match $fixed::<Frac>::checked_from_num(src) {
+                        Some(dst) => Ok(dst),
+                        None if src < 0 => Err(RangeError::NegOverflow(src)),
+                        None => Err(RangeError::PosOverflow(src)),
+                    }
+                }
+            }
+
+            impl<Frac: $leq> crate::ValueFrom<$fixed<Frac>> for $ity {
+                type Err = GeneralError<$fixed<Frac>>;
+                #[inline]
+                #[allow(unused_comparisons)]
+                fn value_from(src: $fixed<Frac>) -> Result<$ity, Self::Err> {
+                    if src.frac() != $fixed::<Frac>::ZERO {
+                        return Err(GeneralError::Unrepresentable(src));
+                    }
+                    match src.checked_to_num() {
+                        Some(dst) => Ok(dst),
+                        None if src.to_bits() < 0 => Err(GeneralError::NegOverflow(src)),
+                        None => Err(GeneralError::PosOverflow(src)),
+                    }
+                }
+            }
+
+            impl<Frac: $leq> crate::ApproxFrom<$fixed<Frac>, crate::Wrapping> for $ity {
+                type Err = NoError;
+                #[inline]
+                fn approx_from(src: $fixed<Frac>) -> Result<$ity, Self::Err> {
+                    Ok(src.wrapping_to_num())
+                }
+            }
+
+            fixed_conv! { @round $fixed<$leq>: $wide, $ity; crate::DefaultApprox => Rounding::Zero }
+            fixed_conv! { @round $fixed<$leq>: $wide, $ity; crate::RoundToNearest => Rounding::Nearest }
+            fixed_conv! { @round $fixed<$leq>: $wide, $ity; crate::RoundHalfEven => Rounding::NearestEven }
+            fixed_conv! { @round $fixed<$leq>: $wide, $ity; crate::RoundToZero => Rounding::Zero }
+            fixed_conv! { @round $fixed<$leq>: $wide, $ity; crate::RoundToNegInf => Rounding::NegInf }
+            fixed_conv! { @round $fixed<$leq>: $wide, $ity; crate::RoundToPosInf => Rounding::PosInf }
+        )*
+    };
+
+    (@round $fixed:ident<$leq:ident>: $wide:ident, $ity:ident; $scheme:ty => $rounding:expr) => {
+        impl<Frac: $leq> crate::ApproxFrom<$fixed<Frac>, $scheme> for $ity {
+            type Err = RangeError<$fixed<Frac>>;
+            #[inline]
+            #[allow(unused_comparisons)]
+            fn approx_from(src: $fixed<Frac>) -> Result<$ity, Self::Err> {
+                let frac = $fixed::<Frac>::FRAC_NBITS;
+                let raw = src.to_bits() as $wide;
+                // Shift in two steps, since `frac` can be the full width.
+                let floor = if frac == 0 { raw } else { raw >> (frac - 1) >> 1 };
+                let rem = (raw as u128) & u128::MAX.checked_shr(128 - frac).unwrap_or(0);
+                let up = round_up(rem, frac, raw < 0, floor & 1 == 1, $rounding);
+                $ity::try_from(floor + up as $wide).map_err(|_| {
+                    if raw < 0 {
+                        RangeError::NegOverflow(src)
+                    } else {
+                        RangeError::PosOverflow(src)
+                    }
+                })
+            }
+        }
+    };
+
+    (@float $fixed:ident<$leq:ident>: $bits:ident, $wide:ident; $($fty:ident),*) => {
+        $(
+            impl<Frac: $leq> crate::ValueFrom<$fixed<Frac>> for $fty {
+                type Err = Unrepresentable<$fixed<Frac>>;
+                #[inline]
+                fn value_from(src: $fixed<Frac>) -> Result<$fty, Self::Err> {
+                    // The nearest float converts back exactly if and only if
+                    // it is equal to the source.
+                    let dst: $fty = src.to_num();
+                    match $fixed::<Frac>::checked_from_num(dst) {
+                        Some(back) if back == src => Ok(dst),
+                        _ => Err(Unrepresentable(src)),
+                    }
+                }
+            }
+
+            impl<Frac: $leq> crate::ValueFrom<$fty> for $fixed<Frac> {
+                type Err = GeneralError<$fty>;
+                #[inline]
+                fn value_from(src: $fty) -> Result<$fixed<Frac>, Self::Err> {
+                    if src.is_nan() {
+                        return Err(GeneralError::Unrepresentable(src));
+                    }
+                    // Scaling by a power of two is exact for any value that
+                    // can be in range, so the bits are exact if and only if
+                    // they have no fractional part.
+                    let scaled = src as f64 * 2f64.powi($fixed::<Frac>::FRAC_NBITS as i32);
+                    if scaled < $bits::MIN as f64 {
+                        return Err(GeneralError::NegOverflow(src));
+                    }
+                    if scaled >= $bits::MAX as f64 + 1.0 {
+                        return Err(GeneralError::PosOverflow(src));
+                    }
+                    if scaled.fract() != 0.0 {
+                        return Err(GeneralError::Unrepresentable(src));
+                    }
+                    Ok($fixed::<Frac>::from_bits(scaled as $bits))
+                }
+            }
+
+            impl<Frac: $leq> crate::ApproxFrom<$fixed<Frac>, crate::DefaultApprox> for $fty {
+                type Err = NoError;
+                #[inline]
+                fn approx_from(src: $fixed<Frac>) -> Result<$fty, Self::Err> {
+                    Ok(src.to_num())
+                }
+            }
+
+            impl<Frac: $leq> crate::ApproxFrom<$fty, crate::Wrapping> for $fixed<Frac> {
+                type Err = FloatError<$fty>;
+                #[inline]
+                fn approx_from(src: $fty) -> Result<$fixed<Frac>, Self::Err> {
+                    if src.is_nan() {
+                        return Err(FloatError::NotANumber(src));
+                    }
+                    if src.is_infinite() {
+                        return Err(if src < 0.0 {
+                            FloatError::NegOverflow(src)
+                        } else {
+                            FloatError::PosOverflow(src)
+                        });
+                    }
+                    let frac = $fixed::<Frac>::FRAC_NBITS;
+                    let approx = scale_round(src as f64, frac, Rounding::NegInf);
+                    Ok($fixed::<Frac>::wrapping_from_num(approx / 2f64.powi(frac as i32)))
+                }
+            }
+
+            fixed_conv! { @scale $fixed<$leq>: $bits, $fty; crate::DefaultApprox => Rounding::NearestEven }
+            fixed_conv! { @scale $fixed<$leq>: $bits, $fty; crate::RoundToNearest => Rounding::Nearest }
+            fixed_conv! { @scale $fixed<$leq>: $bits, $fty; crate::RoundHalfEven => Rounding::NearestEven }
+            fixed_conv! { @scale $fixed<$leq>: $bits, $fty; crate::RoundToZero => Rounding::Zero }
+            fixed_conv! { @scale $fixed<$leq>: $bits, $fty; crate::RoundToNegInf => Rounding::NegInf }
+            fixed_conv! { @scale $fixed<$leq>: $bits, $fty; crate::RoundToPosInf => Rounding::PosInf }
+        )*
+    };
+
+    (@scale $fixed:ident<$leq:ident>: $bits:ident, $fty:ident; $scheme:ty => $rounding:expr) => {
+        impl<Frac: $leq> crate::ApproxFrom<$fty, $scheme> for $fixed<Frac> {
+            type Err = FloatError<$fty>;
+            #[inline]
+            fn approx_from(src: $fty) -> Result<$fixed<Frac>, Self::Err> {
+                if src.is_nan() {
+                    return Err(FloatError::NotANumber(src));
+                }
+                let approx = scale_round(src as f64, $fixed::<Frac>::FRAC_NBITS, $rounding);
+                // Both bounds are powers of two (or zero), so are exact.
+                if approx < $bits::MIN as f64 {
+                    return Err(FloatError::NegOverflow(src));
+                }
+                if approx >= $bits::MAX as f64 + 1.0 {
+                    return Err(FloatError::PosOverflow(src));
+                }
+                Ok($fixed::<Frac>::from_bits(approx as $bits))
+            }
+        }
+    };
+}
+
+fixed_conv! {
+    FixedI8<LeEqU8>: i8, i128;
+    FixedI16<LeEqU16>: i16, i128;
+    FixedI32<LeEqU32>: i32, i128;
+    FixedI64<LeEqU64>: i64, i128;
+    FixedI128<LeEqU128>: i128, i128;
+    FixedU8<LeEqU8>: u8, u128;
+    FixedU16<LeEqU16>: u16, u128;
+    FixedU32<LeEqU32>: u32, u128;
+    FixedU64<LeEqU64>: u64, u128;
+    FixedU128<LeEqU128>: u128, u128;
+}
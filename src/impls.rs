@@ -92,23 +92,26 @@ macro_rules! approx_z_up {
 }
 
 /// A fallible float->int conversion, with an explicit rounding step.
-macro_rules! impl_float2int_round {
-    ($src:ty, $dst:ident, [$min:expr, $max:expr], $scheme:ty, approx: |$src_name:ident| $conv:expr) => {
+///
+/// The source value is range-checked against the inclusive bounds in
+/// `SafeRange`, which guarantee that the rounded value fits in `$dst`.
+macro_rules! impl_float2int {
+    ($src:ident, $dst:ident, $scheme:ident, approx: |$src_name:ident| $conv:expr) => {
         as_item! {
-            impl crate::ApproxFrom<$src, $scheme> for $dst {
+            impl crate::ApproxFrom<$src, crate::$scheme> for $dst {
                 type Err = crate::errors::FloatError<$src>;
                 #[inline]
                 fn approx_from(src: $src) -> Result<$dst, Self::Err> {
                     if src.is_nan() {
                         return Err(crate::errors::FloatError::NotANumber(src));
                     }
-                    let approx = { let $src_name = src; $conv };
-                    if approx < $min  {
+                    if src < crate::SafeRange::<$src, $dst, crate::$scheme>::MIN {
                         return Err(crate::errors::FloatError::NegOverflow(src));
                     }
-                    if approx > $max {
+                    if src > crate::SafeRange::<$src, $dst, crate::$scheme>::MAX {
                         return Err(crate::errors::FloatError::PosOverflow(src));
                     }
+                    let approx = { let $src_name = src; $conv };
                     Ok(unsafe { approx.to_int_unchecked::<$dst>() })
                 }
             }
@@ -116,57 +119,51 @@ macro_rules! impl_float2int_round {
     };
 }
 
-/// A fallible float->int conversion, with an implicit truncation for large integers.
+/// Defines the `const fn` counterpart of an integer `ValueFrom` conversion, on
+/// `konst::Conv<Src, Dst>`.
 ///
-/// By "large integers" we mean those that won't have any fractional part in the
-/// floating point source value.
-///
-/// Limits are specified as the min/max values that succeed.
-macro_rules! impl_float2int_trunc_large {
-    ($src:ty, $dst:ident, [$min:expr, $max:expr], $scheme:ty) => {
+/// Conversions into floats are skipped, since those casts are not `const` on
+/// the minimum supported compiler.
+macro_rules! konst_conv {
+    (($($attrs:tt)*), $src:ty, f32, $err:ty, |$name:ident| $body:expr) => {};
+    (($($attrs:tt)*), $src:ty, f64, $err:ty, |$name:ident| $body:expr) => {};
+    (($($attrs:tt)*), $src:ty, $dst:ty, $err:ty, |$name:ident| $body:expr) => {
         as_item! {
-            impl crate::ApproxFrom<$src, $scheme> for $dst {
-                type Err = crate::errors::FloatError<$src>;
+            $($attrs)*
+            impl crate::konst::Conv<$src, $dst> {
+                /// Performs the conversion; see [`ValueFrom`](crate::ValueFrom).
                 #[inline]
-                fn approx_from(src: $src) -> Result<$dst, Self::Err> {
-                    if src.is_nan() {
-                        return Err(crate::errors::FloatError::NotANumber(src));
-                    }
-                    if src < $min  {
-                        return Err(crate::errors::FloatError::NegOverflow(src));
-                    }
-                    if src > $max {
-                        return Err(crate::errors::FloatError::PosOverflow(src));
+                pub const fn value_from($name: $src) -> Result<$dst, $err> {
+                    $body
+                }
+
+                /// Performs the conversion, panicking if it fails.
+                ///
+                /// In a const context, a failed conversion is a compile error.
+                #[inline]
+                pub const fn value_as(src: $src) -> $dst {
+                    match Self::value_from(src) {
+                        Ok(dst) => dst,
+                        Err(_) => panic!("value out of range for the destination type"),
                     }
-                    Ok(unsafe { src.to_int_unchecked::<$dst>() })
                 }
             }
         }
     };
 }
 
-/// A fallible float->int conversion, with an implicit truncation for small integers.
-///
-/// Limits are specified as the first value that fails, rather than
-/// the first value that succeeds.
-macro_rules! impl_float2int_trunc {
-    ($src:ty, $dst:ident, [$min:expr, $max:expr], $scheme:ty) => {
+/// Describes a `ValueFrom` conversion from `num_conv!` with `misc::ConversionInfo`,
+/// given which directions it can overflow in and the range of source values
+/// that convert successfully.
+macro_rules! conv_info {
+    (($($attrs:tt)*), $src:ty, $dst:ty, [$neg:expr, $pos:expr], [$min:expr, $max:expr]) => {
         as_item! {
-            impl crate::ApproxFrom<$src, $scheme> for $dst {
-                type Err = crate::errors::FloatError<$src>;
-                #[inline]
-                fn approx_from(src: $src) -> Result<$dst, Self::Err> {
-                    if src.is_nan() {
-                        return Err(crate::errors::FloatError::NotANumber(src));
-                    }
-                    if src <= $min  {
-                        return Err(crate::errors::FloatError::NegOverflow(src));
-                    }
-                    if src >= $max {
-                        return Err(crate::errors::FloatError::PosOverflow(src));
-                    }
-                    Ok(unsafe { src.to_int_unchecked::<$dst>() })
-                }
+            $($attrs)*
+            impl crate::misc::ConversionInfo<$src> for $dst {
+                const CAN_NEG_OVERFLOW: bool = $neg;
+                const CAN_POS_OVERFLOW: bool = $pos;
+                const SRC_MIN: $src = $min;
+                const SRC_MAX: $src = $max;
             }
         }
     };
@@ -206,6 +203,10 @@ macro_rules! num_conv {
                     Ok(src as $dst)
                 }
             }
+
+            konst_conv! { ($($attrs)*), $src, $dst, crate::errors::NoError, |src| Ok(src as $dst) }
+
+            conv_info! { ($($attrs)*), $src, $dst, [false, false], [<$src>::MIN, <$src>::MAX] }
         }
         num_conv! { @ $src=> $($tail)* }
     };
@@ -221,15 +222,21 @@ macro_rules! num_conv {
                 type Err = crate::errors::RangeError<$src>;
                 #[inline]
                 fn value_from(src: $src) -> Result<$dst, Self::Err> {
-                    if src < 0 {
-                        return Err(crate::errors::RangeError::NegOverflow(src));
-                    }
-                    if src > $dst::MAX as $src {
-                        return Err(crate::errors::RangeError::PosOverflow(src));
-                    }
-                    Ok(src as $dst)
+                    crate::konst::Conv::<$src, $dst>::value_from(src)
                 }
             }
+
+            konst_conv! { ($($attrs)*), $src, $dst, crate::errors::RangeError<$src>, |src| {
+                if src < 0 {
+                    return Err(crate::errors::RangeError::NegOverflow(src));
+                }
+                if src > $dst::MAX as $src {
+                    return Err(crate::errors::RangeError::PosOverflow(src));
+                }
+                Ok(src as $dst)
+            } }
+
+            conv_info! { ($($attrs)*), $src, $dst, [true, true], [0, $dst::MAX as $src] }
         }
         num_conv! { @ $src=> $($tail)* }
     };
@@ -245,12 +252,18 @@ macro_rules! num_conv {
                 type Err = crate::errors::PosOverflow<$src>;
                 #[inline]
                 fn value_from(src: $src) -> Result<$dst, Self::Err> {
-                    if src > $dst::MAX as $src {
-                        return Err(crate::errors::PosOverflow(src));
-                    }
-                    Ok(src as $dst)
+                    crate::konst::Conv::<$src, $dst>::value_from(src)
                 }
             }
+
+            konst_conv! { ($($attrs)*), $src, $dst, crate::errors::PosOverflow<$src>, |src| {
+                if src > $dst::MAX as $src {
+                    return Err(crate::errors::PosOverflow(src));
+                }
+                Ok(src as $dst)
+            } }
+
+            conv_info! { ($($attrs)*), $src, $dst, [false, true], [<$src>::MIN, $dst::MAX as $src] }
         }
         num_conv! { @ $src=> $($tail)* }
     };
@@ -266,15 +279,21 @@ macro_rules! num_conv {
                 type Err = crate::errors::RangeError<$src>;
                 #[inline]
                 fn value_from(src: $src) -> Result<$dst, Self::Err> {
-                    if src < $dst::MIN as $src {
-                        return Err(crate::errors::RangeError::NegOverflow(src));
-                    }
-                    if src > $dst::MAX as $src {
-                        return Err(crate::errors::RangeError::PosOverflow(src));
-                    }
-                    Ok(src as $dst)
+                    crate::konst::Conv::<$src, $dst>::value_from(src)
                 }
             }
+
+            konst_conv! { ($($attrs)*), $src, $dst, crate::errors::RangeError<$src>, |src| {
+                if src < $dst::MIN as $src {
+                    return Err(crate::errors::RangeError::NegOverflow(src));
+                }
+                if src > $dst::MAX as $src {
+                    return Err(crate::errors::RangeError::PosOverflow(src));
+                }
+                Ok(src as $dst)
+            } }
+
+            conv_info! { ($($attrs)*), $src, $dst, [true, true], [$dst::MIN as $src, $dst::MAX as $src] }
         }
         num_conv! { @ $src=> $($tail)* }
     };
@@ -290,12 +309,18 @@ macro_rules! num_conv {
                 type Err = crate::errors::NegOverflow<$src>;
                 #[inline]
                 fn value_from(src: $src) -> Result<$dst, Self::Err> {
-                    if src < 0 {
-                        return Err(crate::errors::NegOverflow(src));
-                    }
-                    Ok(src as $dst)
+                    crate::konst::Conv::<$src, $dst>::value_from(src)
                 }
             }
+
+            konst_conv! { ($($attrs)*), $src, $dst, crate::errors::NegOverflow<$src>, |src| {
+                if src < 0 {
+                    return Err(crate::errors::NegOverflow(src));
+                }
+                Ok(src as $dst)
+            } }
+
+            conv_info! { ($($attrs)*), $src, $dst, [true, false], [0, <$src>::MAX] }
         }
         num_conv! { @ $src=> $($tail)* }
     };
@@ -314,6 +339,10 @@ macro_rules! num_conv {
                     Ok(src as $dst)
                 }
             }
+
+            konst_conv! { ($($attrs)*), $src, $dst, crate::errors::NoError, |src| Ok(src as $dst) }
+
+            conv_info! { ($($attrs)*), $src, $dst, [false, false], [<$src>::MIN, <$src>::MAX] }
         }
         num_conv! { @ $src=> $($tail)* }
     };
@@ -337,6 +366,8 @@ macro_rules! num_conv {
                     Ok(src as $dst)
                 }
             }
+
+            conv_info! { ($($attrs)*), $src, $dst, [true, true], [-$bound, $bound] }
         }
         num_conv! { @ $src=> $($tail)* }
     };
@@ -356,6 +387,8 @@ macro_rules! num_conv {
                     Ok(src as $dst)
                 }
             }
+
+            conv_info! { ($($attrs)*), $src, $dst, [false, true], [<$src>::MIN, $max] }
         }
         num_conv! { @ $src=> $($tail)* }
     };
@@ -366,25 +399,13 @@ macro_rules! num_conv {
 }
 
 macro_rules! num_conv_float2int {
-    // Convert float->int, using explicit min and max range limits.
-    ($src:ty => [$min:expr, $max:expr] $dst:ident) => {
+    ($src:ident => $dst:ident) => {
         as_item! {
-            impl_float2int_trunc_large! { $src, $dst, [$min, $max], crate::DefaultApprox }
-            impl_float2int_trunc_large! { $src, $dst, [$min, $max], crate::RoundToZero }
-            impl_float2int_round! { $src, $dst, [$min, $max], crate::RoundToNearest, approx: |s| s.round() }
-            impl_float2int_round! { $src, $dst, [$min, $max], crate::RoundToNegInf, approx: |s| s.floor() }
-            impl_float2int_round! { $src, $dst, [$min, $max], crate::RoundToPosInf, approx: |s| s.ceil() }
-        }
-    };
-
-    // Convert float->int, using the destination type's MIN and MAX as the allowed range.
-    ($src:ty => $dst:ident) => {
-        as_item! {
-            impl_float2int_trunc! { $src, $dst, [$dst::MIN as $src - 1.0, $dst::MAX as $src + 1.0], crate::DefaultApprox }
-            impl_float2int_trunc! { $src, $dst, [$dst::MIN as $src - 1.0, $dst::MAX as $src + 1.0], crate::RoundToZero }
-            impl_float2int_round! { $src, $dst, [$dst::MIN as $src, $dst::MAX as $src], crate::RoundToNearest, approx: |s| s.round() }
-            impl_float2int_round! { $src, $dst, [$dst::MIN as $src, $dst::MAX as $src], crate::RoundToNegInf, approx: |s| s.floor() }
-            impl_float2int_round! { $src, $dst, [$dst::MIN as $src, $dst::MAX as $src], crate::RoundToPosInf, approx: |s| s.ceil() }
+            impl_float2int! { $src, $dst, DefaultApprox, approx: |s| s }
+            impl_float2int! { $src, $dst, RoundToZero, approx: |s| s }
+            impl_float2int! { $src, $dst, RoundToNearest, approx: |s| s.round() }
+            impl_float2int! { $src, $dst, RoundToNegInf, approx: |s| s.floor() }
+            impl_float2int! { $src, $dst, RoundToPosInf, approx: |s| s.ceil() }
         }
     };
 }
@@ -416,7 +437,7 @@ mod lang_ints {
 mod lang_floats {
     use crate::errors::{NoError, RangeError};
     use crate::ValueFrom;
-    use crate::{ApproxFrom, ApproxScheme};
+    use crate::{ApproxFrom, ApproxScheme, RoundToOdd};
 
     // f32 -> f64: strictly widening
     impl<Scheme> ApproxFrom<f32, Scheme> for f64
@@ -455,6 +476,34 @@ mod lang_floats {
             Ok(src as f32)
         }
     }
+
+    impl ApproxFrom<f64, RoundToOdd> for f32 {
+        type Err = RangeError<f64>;
+        #[inline]
+        fn approx_from(src: f64) -> Result<f32, Self::Err> {
+            if !src.is_finite() {
+                return Ok(src as f32);
+            }
+            if src < f32::MIN as f64 {
+                return Err(RangeError::NegOverflow(src));
+            }
+            if src > f32::MAX as f64 {
+                return Err(RangeError::PosOverflow(src));
+            }
+            let nearest = src as f32;
+            if nearest as f64 == src {
+                return Ok(nearest);
+            }
+            // Step back towards zero if rounding went away from it, then set
+            // the last bit. Both steps work on the magnitude, whatever the sign.
+            let truncated = if (nearest as f64).abs() > src.abs() {
+                f32::from_bits(nearest.to_bits() - 1)
+            } else {
+                nearest
+            };
+            Ok(f32::from_bits(truncated.to_bits() | 1))
+        }
+    }
 }
 
 mod lang_int_to_float {
@@ -472,48 +521,86 @@ mod lang_int_to_float {
     #[32] w f64, #[64] nf [+- 9_007_199_254_740_992] f64 }
     num_conv! { usize=> nf [, 16_777_216] f32,
     #[32] w f64, #[64] nf [, 9_007_199_254_740_992] f64 }
+
+    /// Truncates `m` to `digits` significant bits, setting the last one if any
+    /// bits were lost. Returns the result and the number of bits dropped.
+    fn round_to_odd(m: u64, digits: u32) -> (u64, u32) {
+        let len = 64 - m.leading_zeros();
+        if len <= digits {
+            return (m, 0);
+        }
+        let shift = len - digits;
+        let inexact = m & ((1 << shift) - 1) != 0;
+        ((m >> shift) | inexact as u64, shift)
+    }
+
+    // The significand fits exactly, and `2^shift` is built from its bits.
+    fn odd_f32(m: u64) -> f32 {
+        let (sig, shift) = round_to_odd(m, f32::MANTISSA_DIGITS);
+        sig as f32 * f32::from_bits((127 + shift) << 23)
+    }
+
+    fn odd_f64(m: u64) -> f64 {
+        let (sig, shift) = round_to_odd(m, f64::MANTISSA_DIGITS);
+        sig as f64 * f64::from_bits((1023 + shift as u64) << 52)
+    }
+
+    macro_rules! int_to_float_odd {
+        ($fty:ident, $odd:ident; signed: $($ity:ident),*) => {
+            $(
+                impl crate::ApproxFrom<$ity, crate::RoundToOdd> for $fty {
+                    type Err = crate::errors::NoError;
+                    #[inline]
+                    fn approx_from(src: $ity) -> Result<$fty, Self::Err> {
+                        let odd = $odd(src.unsigned_abs() as u64);
+                        Ok(if src < 0 { -odd } else { odd })
+                    }
+                }
+            )*
+        };
+        ($fty:ident, $odd:ident; unsigned: $($uty:ident),*) => {
+            $(
+                impl crate::ApproxFrom<$uty, crate::RoundToOdd> for $fty {
+                    type Err = crate::errors::NoError;
+                    #[inline]
+                    fn approx_from(src: $uty) -> Result<$fty, Self::Err> {
+                        Ok($odd(src as u64))
+                    }
+                }
+            )*
+        };
+    }
+
+    int_to_float_odd! { f32, odd_f32; signed: i8, i16, i32, i64, isize }
+    int_to_float_odd! { f32, odd_f32; unsigned: u8, u16, u32, u64, usize }
+    int_to_float_odd! { f64, odd_f64; signed: i8, i16, i32, i64, isize }
+    int_to_float_odd! { f64, odd_f64; unsigned: u8, u16, u32, u64, usize }
 }
 
 mod lang_float_to_int {
-
-    // Some limits need to be specified as floating point values, because it's
-    // possible for an integer limit to be imprecise once cast to floating point.
-    // Some of these values are equal to the integer MIN and MAX values cast to
-    // floats, and some of them are not, because the cast-to-float sometimes
-    // rounds up past the value that can safely be cast back to integer.
+    // The range checks use the bounds in `SafeRange`; see `limits.rs`.
 
     num_conv_float2int!(f32 => i8);
     num_conv_float2int!(f32 => i16);
-    num_conv_float2int!(f32 => [crate::MIN_F32_I32, crate::MAX_F32_I32] i32);
-    num_conv_float2int!(f32 => [crate::MIN_F32_I64, crate::MAX_F32_I64] i64);
+    num_conv_float2int!(f32 => i32);
+    num_conv_float2int!(f32 => i64);
+    num_conv_float2int!(f32 => isize);
 
     num_conv_float2int!(f32 => u8);
     num_conv_float2int!(f32 => u16);
-    num_conv_float2int!(f32 => [0.0, crate::MAX_F32_U32] u32);
-    num_conv_float2int!(f32 => [0.0, crate::MAX_F32_U64] u64);
-
-    num_conv_float2int!(f32 => [crate::MIN_F32_ISIZE, crate::MAX_F32_ISIZE] isize);
-    num_conv_float2int!(f32 => [0.0, crate::MAX_F32_USIZE] usize);
+    num_conv_float2int!(f32 => u32);
+    num_conv_float2int!(f32 => u64);
+    num_conv_float2int!(f32 => usize);
 
     num_conv_float2int!(f64 => i8);
     num_conv_float2int!(f64 => i16);
     num_conv_float2int!(f64 => i32);
-    num_conv_float2int!(f64 => [crate::MIN_F64_I64, crate::MAX_F64_I64] i64);
+    num_conv_float2int!(f64 => i64);
+    num_conv_float2int!(f64 => isize);
 
     num_conv_float2int!(f64 => u8);
     num_conv_float2int!(f64 => u16);
     num_conv_float2int!(f64 => u32);
-    num_conv_float2int!(f64 => [0.0, crate::MAX_F64_U64] u64);
-
-    #[cfg(target_pointer_width = "32")]
-    mod size32 {
-        num_conv_float2int!(f64 => isize);
-        num_conv_float2int!(f64 => usize);
-    }
-
-    #[cfg(target_pointer_width = "64")]
-    mod size64 {
-        num_conv_float2int!(f64 => [crate::MIN_F64_I64, crate::MAX_F64_I64] isize);
-        num_conv_float2int!(f64 => [0.0, crate::MAX_F64_U64] usize);
-    }
+    num_conv_float2int!(f64 => u64);
+    num_conv_float2int!(f64 => usize);
 }
@@ -0,0 +1,167 @@
+//! This module provides conversions for the integer types that show up when
+//! doing I/O: `u64` stream positions and file offsets, `usize` buffer lengths
+//! and indices, and `isize` pointer differences (such as those returned by
+//! `pointer::offset_from`).
+//!
+//! The helper functions report failed conversions as an [`io::Error`] of kind
+//! [`ErrorKind::InvalidInput`], so they can be used with the `?` operator in
+//! functions that return [`io::Result`].
+//!
+//! In addition, `ValueFrom` converts between [`SeekFrom`] and every
+//! primitive integer type:
+//!
+//! - An integer becomes an absolute [`SeekFrom::Start`] position. Negative
+//!   offsets fail with `NegOverflow`.
+//! - Any position becomes the offset it carries: the absolute `u64` of a
+//!   [`SeekFrom::Start`], or the relative `i64` of a [`SeekFrom::Current`]
+//!   or [`SeekFrom::End`]. Offsets that do not fit fail with `NegOverflow`
+//!   or `PosOverflow`, carrying the original position. Use [`start_offset`]
+//!   to accept absolute positions only.
+//!
+//! # Examples
+//!
+//! ```
+//! # use conv2::*;
+//! use std::io::{ErrorKind, SeekFrom};
+//!
+//! assert_eq!(conv2::io::u64_to_usize(4096).unwrap(), 4096usize);
+//! assert_eq!(conv2::io::isize_to_usize(-1).unwrap_err().kind(), ErrorKind::InvalidInput);
+//!
+//! assert_eq!(SeekFrom::value_from(512i64), Ok(SeekFrom::Start(512)));
+//! assert_eq!(SeekFrom::value_from(-512i64), Err(NegOverflow(-512)));
+//! assert_eq!(i64::value_from(SeekFrom::Start(512)), Ok(512));
+//! assert_eq!(u8::value_from(SeekFrom::Start(512)), Err(GeneralError::PosOverflow(SeekFrom::Start(512))));
+//! assert_eq!(i64::value_from(SeekFrom::End(-1)), Ok(-1));
+//! assert_eq!(u64::value_from(SeekFrom::End(-1)), Err(GeneralError::NegOverflow(SeekFrom::End(-1))));
+//! assert_eq!(conv2::io::start_offset(SeekFrom::Start(512)).unwrap(), 512);
+//! assert!(conv2::io::start_offset(SeekFrom::Current(0)).is_err());
+//! ```
+
+use std::fmt::Debug;
+use std::io::{self, ErrorKind, SeekFrom};
+
+use crate::errors::{ConversionError, GeneralError, Unrepresentable};
+use crate::{ValueFrom, ValueInto};
+
+/// Performs a value conversion, reporting failure as an I/O error.
+///
+/// The error has kind [`ErrorKind::InvalidInput`], and wraps a
+/// [`ConversionError`] naming the value along with the source and destination
+/// types.
+pub fn value_into<Src, Dst>(src: Src) -> io::Result<Dst>
+where
+    Src: ValueInto<Dst> + Debug,
+    Src::Err: Into<GeneralError<Src>>,
+{
+    src.value_into().map_err(|e| {
+        io::Error::new(
+            ErrorKind::InvalidInput,
+            ConversionError::new::<Dst, _, _>(e),
+        )
+    })
+}
+
+/// Converts a `u64` length or file offset into a `usize` buffer length.
+#[inline]
+pub fn u64_to_usize(len: u64) -> io::Result<usize> {
+    value_into(len)
+}
+
+/// Converts a `usize` buffer length into a `u64` length or file offset.
+#[inline]
+pub fn usize_to_u64(len: usize) -> io::Result<u64> {
+    value_into(len)
+}
+
+/// Converts an `isize` pointer difference into a `usize` length.
+///
+/// This fails if the difference is negative.
+#[inline]
+pub fn isize_to_usize(offset: isize) -> io::Result<usize> {
+    value_into(offset)
+}
+
+/// Converts a `usize` length into an `isize` pointer offset.
+#[inline]
+pub fn usize_to_isize(len: usize) -> io::Result<isize> {
+    value_into(len)
+}
+
+/// Converts a `u64` position into an `i64` offset suitable for
+/// [`SeekFrom::Current`] or [`SeekFrom::End`].
+#[inline]
+pub fn u64_to_i64(pos: u64) -> io::Result<i64> {
+    value_into(pos)
+}
+
+/// Returns the absolute offset of a [`SeekFrom::Start`] position.
+///
+/// Relative positions ([`SeekFrom::Current`] and [`SeekFrom::End`]) do not
+/// have an absolute offset, and produce an [`ErrorKind::InvalidInput`] error.
+pub fn start_offset(pos: SeekFrom) -> io::Result<u64> {
+    match pos {
+        SeekFrom::Start(offset) => Ok(offset),
+        SeekFrom::Current(_) | SeekFrom::End(_) => Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            Unrepresentable(pos),
+        )),
+    }
+}
+
+item_for_each! {
+    (u8), (u16), (u32), (u64), (usize) => {
+        ($ity:ident) => {
+            impl crate::ValueFrom<$ity> for SeekFrom {
+                type Err = crate::errors::NoError;
+                #[inline]
+                fn value_from(src: $ity) -> Result<SeekFrom, Self::Err> {
+                    Ok(SeekFrom::Start(src as u64))
+                }
+            }
+        };
+    }
+}
+
+item_for_each! {
+    (i8), (i16), (i32), (i64), (isize) => {
+        ($ity:ident) => {
+            impl crate::ValueFrom<$ity> for SeekFrom {
+                type Err = crate::errors::NegOverflow<$ity>;
+                #[inline]
+                fn value_from(src: $ity) -> Result<SeekFrom, Self::Err> {
+                    if src < 0 {
+                        return Err(crate::errors::NegOverflow(src));
+                    }
+                    Ok(SeekFrom::Start(src as u64))
+                }
+            }
+        };
+    }
+}
+
+item_for_each! {
+    (i8), (i16), (i32), (i64), (isize), (u8), (u16), (u32), (u64), (usize) => {
+        ($ity:ident) => {
+            impl ValueFrom<SeekFrom> for $ity {
+                type Err = GeneralError<SeekFrom>;
+                #[inline]
+                fn value_from(src: SeekFrom) -> Result<$ity, Self::Err> {
+                    match src {
+                        SeekFrom::Start(offset) => {
+                            $ity::value_from(offset).map_err(|_| GeneralError::PosOverflow(src))
+                        }
+                        SeekFrom::Current(offset) | SeekFrom::End(offset) => {
+                            $ity::value_from(offset).map_err(|_| {
+                                if offset < 0 {
+                                    GeneralError::NegOverflow(src)
+                                } else {
+                                    GeneralError::PosOverflow(src)
+                                }
+                            })
+                        }
+                    }
+                }
+            }
+        };
+    }
+}
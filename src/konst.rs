@@ -0,0 +1,69 @@
+//! This module provides `const fn` versions of the value conversions between
+//! the builtin integer types.
+//!
+//! Trait methods cannot be called in a const context, so `ValueFrom` cannot
+//! be used to compute constants. Instead, each integer `ValueFrom` conversion
+//! has a counterpart on [`Conv<Src, Dst>`](Conv), with the same checks and
+//! the same error type:
+//!
+//! - [`Conv::value_from`] returns a `Result`, exactly like
+//!   `ValueFrom::value_from`.
+//! - [`Conv::value_as`] panics if the conversion fails. When used to
+//!   initialise a `const` or `static`, this makes a failed conversion a
+//!   compile error.
+//!
+//! The [`const_value_as!`](crate::const_value_as) macro is shorthand for
+//! `Conv::value_as`.
+//!
+//! Conversions into floating-point types are not included.
+//!
+//! # Examples
+//!
+//! ```
+//! use conv2::konst::Conv;
+//! use conv2::{const_value_as, PosOverflow, RangeError};
+//!
+//! const BUF_SIZE: usize = 4096;
+//! const BUF_LEN: u16 = const_value_as!(usize => u16, BUF_SIZE);
+//! assert_eq!(BUF_LEN, 4096);
+//!
+//! const TOO_BIG: Result<u8, PosOverflow<u64>> = Conv::<u64, u8>::value_from(300);
+//! assert_eq!(TOO_BIG, Err(PosOverflow(300)));
+//! assert_eq!(Conv::<i32, u16>::value_from(-1), Err(RangeError::NegOverflow(-1)));
+//! ```
+//!
+//! A conversion that fails while evaluating a constant does not compile:
+//!
+//! ```compile_fail
+//! const BUF_LEN: u16 = conv2::const_value_as!(usize => u16, 65_536);
+//! ```
+
+use std::marker::PhantomData;
+
+/// Provides `const fn` conversions from `Src` to `Dst`.
+///
+/// This type cannot be constructed; its conversions are associated
+/// functions. See the [module documentation](self) for details.
+pub struct Conv<Src, Dst>(PhantomData<fn(Src) -> Dst>);
+
+/// Converts a value between builtin integer types in a const context,
+/// panicking if the value does not fit.
+///
+/// `const_value_as!(Src => Dst, value)` is shorthand for
+/// [`Conv::<Src, Dst>::value_as(value)`](crate::konst::Conv). When used to
+/// initialise a `const` or `static`, a failed conversion is a compile error.
+///
+/// # Examples
+///
+/// ```
+/// # use conv2::const_value_as;
+/// const PAGE: u64 = 4096;
+/// const PAGE_WORDS: u32 = const_value_as!(u64 => u32, PAGE / 8);
+/// assert_eq!(PAGE_WORDS, 512);
+/// ```
+#[macro_export]
+macro_rules! const_value_as {
+    ($src:ty => $dst:ty, $value:expr) => {
+        $crate::konst::Conv::<$src, $dst>::value_as($value)
+    };
+}
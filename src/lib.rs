@@ -19,6 +19,30 @@
 //!
 //! `conv2` is compatible with Rust 1.61 and higher.
 //!
+//! ## Optional Features
+//!
+//! - `serde`: implements `Serialize` and `Deserialize` for the error types,
+//!   and provides helpers for converting values during deserialization (see
+//!   the `de` module).
+//! - `num-traits`: bridges this crate's conversion traits and `num-traits`'
+//!   `ToPrimitive`/`FromPrimitive`/`NumCast` (see the `num_compat` module).
+//! - `num-bigint`: implements conversions between the builtin numeric types
+//!   and `num-bigint`'s `BigInt` and `BigUint` (see the `bigint` module).
+//! - `num-complex`: implements component-wise conversions between
+//!   `num-complex`'s `Complex` types (see the `complex` module).
+//! - `rust_decimal`: implements conversions between the builtin numeric types
+//!   and `rust_decimal`'s `Decimal` (see the `decimal` module). This feature
+//!   requires a newer compiler than the rest of the crate.
+//! - `fixed`: implements conversions between the builtin numeric types and
+//!   the `fixed` crate's fixed-point types (see the `fixed_point` module).
+//!   This feature requires a newer compiler than the rest of the crate.
+//! - `quickcheck`: provides property checks that other crates can use to test
+//!   their own conversions (see the `testing` module). This feature requires
+//!   a newer compiler than the rest of the crate.
+//! - `proptest`: provides `proptest` strategies that generate values near the
+//!   bounds of conversions (see the `strategy` module). This feature requires
+//!   a newer compiler than the rest of the crate.
+//!
 //! # Overview
 //!
 //! The following traits are used to define various conversion semantics:
@@ -43,6 +67,9 @@
 //! - [`ConvUtil::try_as<Dst>`] - converts to `Dst` using `TryInto::try_into`.
 //! - [`ConvUtil::value_as<Dst>`] - converts to `Dst` using
 //!   `ValueInto::value_into`.
+//! - [`ConvUtil::value_into_infallible<Dst>`] - converts to `Dst` using
+//!   `ValueInto::value_into`, where the conversion cannot fail (see
+//!   [`InfallibleFrom`]).
 //! - [`ConvAsUtil::approx`] - approximates to an inferred destination type
 //!   with the `DefaultApprox` scheme.
 //! - [`ConvAsUtil::approx_by`] - approximates to an inferred destination type
@@ -55,6 +82,20 @@
 //!   "invalid" sentinel value on failure.
 //! - [`UnwrapOrSaturate::unwrap_or_saturate`] - saturates to the maximum or
 //!   minimum value of the target type on failure.
+//! - [`WithContext::with_context`] - records the source and destination types
+//!   and the destination range in the error.
+//! - [`arith::ValueArith`] - checked integer arithmetic (`add_value`,
+//!   `sub_value`, *etc.*) that reports failures as an `ArithError` carrying both
+//!   operands, converting the operand with `ValueInto` first.
+//! - [`stochastic::StochasticInto::approx_with_rng`] - rounds up or down at
+//!   random, with a probability given by the fractional part.
+//! - [`bounded::Bounded`] - an integer restricted to a custom range, which
+//!   conversions check against and saturate to.
+//! - [`ranged::RangedI32`] *etc.* - integers whose range is part of the type,
+//!   so that converting between nested ranges is checked at build time.
+//! - [`batch::approx_slice`] and [`batch::saturate_slice`] - float → integer
+//!   conversions over whole slices, written so that the compiler can vectorize
+//!   them.
 //!
 //! ## Provided Implementations
 //!
@@ -70,6 +111,14 @@
 //! *exactly* succeed) and `f64 → f32` (for the same reason). `ApproxFrom`
 //! conversions with the `DefaultApprox` scheme exist between all pairs.
 //! `ApproxFrom` with the `Wrapping` scheme exist between integers.
+//! `ApproxFrom` with the `RoundToOdd` scheme exist for `f64 → f32` and integer
+//! → float.
+//!
+//! The `ValueFrom` conversions between integers are also available as `const fn`s
+//! in the [`konst`] module, for use when computing constants.
+//!
+//! The exact range of source values that each float ↔ integer `ApproxFrom`
+//! conversion accepts is available from [`SafeRange`].
 //!
 //! ## Errors
 //!
@@ -197,9 +246,9 @@
 #![deny(missing_docs)]
 
 pub use crate::errors::{
-    FloatError, GeneralError, GeneralErrorKind, NegOverflow, NoError, PosOverflow, RangeError,
-    RangeErrorKind, Saturate, Unrepresentable, UnwrapOk, UnwrapOrInf, UnwrapOrInvalid,
-    UnwrapOrSaturate,
+    ContextError, ConvError, ConversionError, FloatError, GeneralError, GeneralErrorKind,
+    MapPayload, NegOverflow, NoError, PosOverflow, RangeError, RangeErrorKind, Saturate,
+    Unrepresentable, UnwrapOk, UnwrapOrInf, UnwrapOrInvalid, UnwrapOrSaturate, WithContext,
 };
 
 /// Publicly re-exports the most generally useful set of items.
@@ -208,16 +257,14 @@ pub use crate::errors::{
 /// likely *not* be removed without bumping the major version, new items *may*
 /// be added, which could potentially cause name conflicts in user code.
 pub mod prelude {
+    pub use super::arith::ValueArith;
     pub use super::{
         ApproxFrom, ApproxInto, ConvAsUtil, ConvUtil, GeneralError, GeneralErrorKind,
-        RoundToNearest, RoundToZero, Saturate, UnwrapOk, UnwrapOrInf, UnwrapOrInvalid,
-        UnwrapOrSaturate, ValueFrom, ValueInto, Wrapping,
+        InfallibleFrom, RoundToNearest, RoundToZero, Saturate, UnwrapOk, UnwrapOrInf,
+        UnwrapOrInvalid, UnwrapOrSaturate, ValueFrom, ValueInto, WithContext, Wrapping,
     };
 }
 
-mod limits;
-pub use limits::*;
-
 macro_rules! as_item {
     ($($i:item)*) => {$($i)*};
 }
@@ -236,10 +283,35 @@ macro_rules! item_for_each {
     };
 }
 
+pub mod arith;
+pub mod batch;
+#[cfg(feature = "num-bigint")]
+pub mod bigint;
+pub mod bounded;
+#[cfg(feature = "num-complex")]
+pub mod complex;
+#[cfg(feature = "serde")]
+pub mod de;
+#[cfg(feature = "rust_decimal")]
+pub mod decimal;
 pub mod errors;
+#[cfg(feature = "fixed")]
+pub mod fixed_point;
+pub mod io;
+pub mod konst;
 pub mod misc;
+#[cfg(feature = "num-traits")]
+pub mod num_compat;
+pub mod ranged;
+pub mod stochastic;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(feature = "quickcheck")]
+pub mod testing;
 
 mod impls;
+mod limits;
+pub use limits::*;
 
 /// This trait is used to perform a conversion that is permitted to approximate the
 /// result, but *not* to wrap or saturate the result to fit into the destination
@@ -346,6 +418,12 @@ impl ApproxScheme for Wrapping {}
 pub enum RoundToNearest {}
 impl ApproxScheme for RoundToNearest {}
 
+/// This scheme is used to convert a value by rounding it to the nearest
+/// representable value, with ties rounding to the nearest even value
+/// ("banker's rounding").
+pub enum RoundHalfEven {}
+impl ApproxScheme for RoundHalfEven {}
+
 /// This scheme is used to convert a value by rounding it toward negative
 /// infinity to the nearest representable value.
 pub enum RoundToNegInf {}
@@ -361,6 +439,34 @@ impl ApproxScheme for RoundToPosInf {}
 pub enum RoundToZero {}
 impl ApproxScheme for RoundToZero {}
 
+/// This scheme is used to convert a value by rounding it toward zero, then
+/// setting the last bit of the result if it is inexact ("round to odd").
+///
+/// This is useful when narrowing a float in several steps. Rounding to the
+/// nearest value at each step can give a different result to rounding
+/// directly ("double rounding"). Rounding to odd first, to a type with at
+/// least two more bits of precision than the final type, avoids this: a
+/// subsequent round-to-nearest step then gives the same result as rounding
+/// directly.
+///
+/// This is implemented for `f64` → `f32`, and integer → float conversions.
+///
+/// # Examples
+///
+/// ```
+/// # use conv2::*;
+/// let x = (1u64 << 60) + (1 << 36) + 1;
+///
+/// // Rounding twice loses the `+ 1`, which decides the final rounding.
+/// assert_eq!(x as f64 as f32, (1u64 << 60) as f32);
+/// assert_eq!(x as f32, ((1u64 << 60) + (1 << 37)) as f32);
+///
+/// let odd: f64 = x.approx_as_by::<_, RoundToOdd>().unwrap();
+/// assert_eq!(odd as f32, x as f32);
+/// ```
+pub enum RoundToOdd {}
+impl ApproxScheme for RoundToOdd {}
+
 /// This trait is used to perform an exact, value-preserving conversion.
 ///
 /// Where possible, prefer *implementing* this trait over `ValueInto`, but
@@ -410,6 +516,52 @@ where
     }
 }
 
+/// This trait marks value conversions that cannot fail.
+///
+/// It is implemented for exactly those `ValueFrom` conversions whose error
+/// type is `NoError`. Which conversions those are can depend on the target
+/// platform; for example, `ValueFrom<usize> for u64` cannot fail on 32- and
+/// 64-bit targets. Requiring this trait (or using [`assert_infallible!`])
+/// turns a conversion becoming fallible into a compile error.
+///
+/// See also [`ConvUtil::value_into_infallible`].
+pub trait InfallibleFrom<Src>: ValueFrom<Src, Err = NoError> {
+    /// Convert the given value into an exactly equivalent representation.
+    fn infallible_from(src: Src) -> Self {
+        match Self::value_from(src) {
+            Ok(dst) => dst,
+            Err(e) => match e {},
+        }
+    }
+}
+
+impl<Src, Dst> InfallibleFrom<Src> for Dst where Dst: ValueFrom<Src, Err = NoError> {}
+
+/// Asserts at compile time that value conversions cannot fail.
+///
+/// Each `Src => Dst` pair must implement [`InfallibleFrom`]; that is, the
+/// `ValueFrom<Src> for Dst` conversion must exist and have the error type
+/// `NoError`. Otherwise, compilation fails.
+///
+/// # Examples
+///
+/// ```
+/// conv2::assert_infallible!(u8 => u16, usize => u64, i32 => f64);
+/// ```
+///
+/// ```compile_fail
+/// conv2::assert_infallible!(u64 => u32);
+/// ```
+#[macro_export]
+macro_rules! assert_infallible {
+    ($($src:ty => $dst:ty),+ $(,)?) => {
+        const _: fn() = || {
+            fn assert_infallible<Src, Dst: $crate::InfallibleFrom<Src>>() {}
+            $(assert_infallible::<$src, $dst>();)+
+        };
+    };
+}
+
 /// This extension trait exists to simplify using various conversions.
 ///
 /// If there is more than one implementation for a given type/trait pair, a
@@ -463,6 +615,16 @@ pub trait ConvUtil {
     {
         self.value_into()
     }
+
+    /// Perform a value conversion of the subject to a given type, where the
+    /// conversion cannot fail.
+    fn value_into_infallible<Dst>(self) -> Dst
+    where
+        Self: Sized,
+        Dst: InfallibleFrom<Self>,
+    {
+        Dst::infallible_from(self)
+    }
 }
 
 impl<T> ConvUtil for T {}
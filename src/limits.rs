@@ -1,58 +1,299 @@
-// Some floating-point-to-integer conversions start to lose precision before they
-// reach the min/max values in the destination type. These values represent the
-// safety limits of `to_int_unchecked`.
-
-/// Maximum `f32` that can be represented in an `i32`.
-#[doc(hidden)]
-pub const MAX_F32_I32: f32 = 2.1474835e9;
-/// Minimum `f32` that can be represented in an `i32`.
-#[doc(hidden)]
-pub const MIN_F32_I32: f32 = -2.1474836e9;
-/// Maximum `f32` that can be represented in an `i64`.
-#[doc(hidden)]
-pub const MAX_F32_I64: f32 = 9.2233715e18;
-/// Minimum `f32` that can be represented in an `i64`.
-#[doc(hidden)]
-pub const MIN_F32_I64: f32 = -9.223372e18;
-/// Maximum `f32` that can be represented in a `u32`.
-#[doc(hidden)]
-pub const MAX_F32_U32: f32 = 4.294967e9;
-/// Maximum `f32` that can be represented in a `u64`.
-#[doc(hidden)]
-pub const MAX_F32_U64: f32 = 1.8446743e19;
+// @generated by `tools/safe_float_to_int`; do not edit by hand.
+//
+// The exact bounds of the float-to-integer conversions. Some conversions
+// start to lose precision before they reach the min/max values in the
+// destination type, so the bounds are given as floating-point literals.
+// Each bound is also a safe input to `to_int_unchecked` after rounding.
+
+use std::marker::PhantomData;
+
+use crate::{DefaultApprox, RoundToNearest, RoundToNegInf, RoundToPosInf, RoundToZero};
+
+/// The range of `Src` values that convert successfully into `Dst` under the
+/// approximation scheme `Scheme`.
+///
+/// `MIN` and `MAX` are the smallest and largest source values that convert
+/// successfully; both are inclusive. Any value outside this range (or NaN)
+/// fails to convert. These are the exact bounds used by the `ApproxFrom`
+/// implementations, so they can be used to clamp values before converting
+/// them in bulk.
+///
+/// This is provided for every float → integer conversion, under every scheme
+/// it supports, and for every integer → float conversion under
+/// `DefaultApprox` (which cannot fail, so the range is the whole type). The
+/// float → `i128`/`u128` bounds are also provided, for clamping values before
+/// an `as` cast, although this crate does not implement those conversions.
+///
+/// # Examples
+///
+/// ```
+/// # use conv2::*;
+/// assert_eq!(SafeRange::<f32, i32>::MAX, 2_147_483_520.0);
+/// assert_eq!(SafeRange::<f32, i32>::MAX.approx_as::<i32>(), Ok(2_147_483_520));
+///
+/// // Rounding schemes have different bounds.
+/// assert_eq!(SafeRange::<f64, u8, RoundToNearest>::MIN, -0.49999999999999994);
+/// assert_eq!(SafeRange::<f64, u8, RoundToNegInf>::MIN, 0.0);
+/// assert_eq!(SafeRange::<f64, u8, RoundToPosInf>::MAX, 255.0);
+///
+/// let clamp = |x: f64| x.clamp(SafeRange::<f64, u8>::MIN, SafeRange::<f64, u8>::MAX);
+/// assert_eq!(clamp(1e10).approx_as::<u8>(), Ok(255));
+/// assert_eq!(clamp(-1e10).approx_as::<u8>(), Ok(0));
+/// ```
+pub struct SafeRange<Src, Dst, Scheme = DefaultApprox>(PhantomData<fn(Src) -> (Dst, Scheme)>);
+
+macro_rules! safe_range {
+    ($src:ident => $dst:ident: $($scheme:ident [$min:expr, $max:expr],)*) => {
+        $(
+            impl SafeRange<$src, $dst, $scheme> {
+                /// The smallest source value that converts successfully.
+                pub const MIN: $src = $min;
+
+                /// The largest source value that converts successfully.
+                pub const MAX: $src = $max;
+            }
+        )*
+    };
+}
+
+// Integer-to-float conversions cannot fail.
+item_for_each! {
+    (i8), (i16), (i32), (i64), (isize), (u8), (u16), (u32), (u64), (usize) => {
+        ($ity:ident) => {
+            safe_range! { $ity => f32: DefaultApprox [$ity::MIN, $ity::MAX], }
+            safe_range! { $ity => f64: DefaultApprox [$ity::MIN, $ity::MAX], }
+        };
+    }
+}
+
+safe_range! { f32 => i8:
+    DefaultApprox [-128.99998, 127.99999],
+    RoundToZero [-128.99998, 127.99999],
+    RoundToNearest [-128.49998, 127.49999],
+    RoundToNegInf [-128.0, 127.99999],
+    RoundToPosInf [-128.99998, 127.0],
+}
+
+safe_range! { f32 => i16:
+    DefaultApprox [-32768.996, 32767.998],
+    RoundToZero [-32768.996, 32767.998],
+    RoundToNearest [-32768.496, 32767.498],
+    RoundToNegInf [-32768.0, 32767.998],
+    RoundToPosInf [-32768.996, 32767.0],
+}
+
+safe_range! { f32 => i32:
+    DefaultApprox [-2147483600.0, 2147483500.0],
+    RoundToZero [-2147483600.0, 2147483500.0],
+    RoundToNearest [-2147483600.0, 2147483500.0],
+    RoundToNegInf [-2147483600.0, 2147483500.0],
+    RoundToPosInf [-2147483600.0, 2147483500.0],
+}
+
+safe_range! { f32 => i64:
+    DefaultApprox [-9.223372e18, 9.2233715e18],
+    RoundToZero [-9.223372e18, 9.2233715e18],
+    RoundToNearest [-9.223372e18, 9.2233715e18],
+    RoundToNegInf [-9.223372e18, 9.2233715e18],
+    RoundToPosInf [-9.223372e18, 9.2233715e18],
+}
+
+safe_range! { f32 => u8:
+    DefaultApprox [-0.99999994, 255.99998],
+    RoundToZero [-0.99999994, 255.99998],
+    RoundToNearest [-0.49999997, 255.49998],
+    RoundToNegInf [-0.0, 255.99998],
+    RoundToPosInf [-0.99999994, 255.0],
+}
+
+safe_range! { f32 => u16:
+    DefaultApprox [-0.99999994, 65535.996],
+    RoundToZero [-0.99999994, 65535.996],
+    RoundToNearest [-0.49999997, 65535.496],
+    RoundToNegInf [-0.0, 65535.996],
+    RoundToPosInf [-0.99999994, 65535.0],
+}
+
+safe_range! { f32 => u32:
+    DefaultApprox [-0.0, 4294967000.0],
+    RoundToZero [-0.0, 4294967000.0],
+    RoundToNearest [-0.49999997, 4294967000.0],
+    RoundToNegInf [-0.0, 4294967000.0],
+    RoundToPosInf [-0.99999994, 4294967000.0],
+}
+
+safe_range! { f32 => u64:
+    DefaultApprox [-0.0, 1.8446743e19],
+    RoundToZero [-0.0, 1.8446743e19],
+    RoundToNearest [-0.49999997, 1.8446743e19],
+    RoundToNegInf [-0.0, 1.8446743e19],
+    RoundToPosInf [-0.99999994, 1.8446743e19],
+}
 
 #[cfg(target_pointer_width = "32")]
-/// Maximum `f32` that can be represented in an `isize`.
-#[doc(hidden)]
-pub const MAX_F32_ISIZE: f32 = MAX_F32_I32;
-#[cfg(target_pointer_width = "32")]
-/// Minimum `f32` that can be represented in an `isize`.
-#[doc(hidden)]
-pub const MIN_F32_ISIZE: f32 = MIN_F32_I32;
+safe_range! { f32 => isize:
+    DefaultApprox [-2147483600.0, 2147483500.0],
+    RoundToZero [-2147483600.0, 2147483500.0],
+    RoundToNearest [-2147483600.0, 2147483500.0],
+    RoundToNegInf [-2147483600.0, 2147483500.0],
+    RoundToPosInf [-2147483600.0, 2147483500.0],
+}
+
+#[cfg(target_pointer_width = "64")]
+safe_range! { f32 => isize:
+    DefaultApprox [-9.223372e18, 9.2233715e18],
+    RoundToZero [-9.223372e18, 9.2233715e18],
+    RoundToNearest [-9.223372e18, 9.2233715e18],
+    RoundToNegInf [-9.223372e18, 9.2233715e18],
+    RoundToPosInf [-9.223372e18, 9.2233715e18],
+}
+
 #[cfg(target_pointer_width = "32")]
-/// Maximum `f32` that can be represented in a `usize`.
-#[doc(hidden)]
-pub const MAX_F32_USIZE: f32 = MAX_F32_U32;
+safe_range! { f32 => usize:
+    DefaultApprox [-0.0, 4294967000.0],
+    RoundToZero [-0.0, 4294967000.0],
+    RoundToNearest [-0.49999997, 4294967000.0],
+    RoundToNegInf [-0.0, 4294967000.0],
+    RoundToPosInf [-0.99999994, 4294967000.0],
+}
 
 #[cfg(target_pointer_width = "64")]
-/// Maximum `f32` that can be represented in an `isize`.
-#[doc(hidden)]
-pub const MAX_F32_ISIZE: f32 = MAX_F32_I64;
+safe_range! { f32 => usize:
+    DefaultApprox [-0.0, 1.8446743e19],
+    RoundToZero [-0.0, 1.8446743e19],
+    RoundToNearest [-0.49999997, 1.8446743e19],
+    RoundToNegInf [-0.0, 1.8446743e19],
+    RoundToPosInf [-0.99999994, 1.8446743e19],
+}
+
+safe_range! { f32 => i128:
+    DefaultApprox [-1.7014118e38, 1.7014117e38],
+    RoundToZero [-1.7014118e38, 1.7014117e38],
+    RoundToNearest [-1.7014118e38, 1.7014117e38],
+    RoundToNegInf [-1.7014118e38, 1.7014117e38],
+    RoundToPosInf [-1.7014118e38, 1.7014117e38],
+}
+
+safe_range! { f32 => u128:
+    DefaultApprox [-0.99999994, 3.4028235e38],
+    RoundToZero [-0.99999994, 3.4028235e38],
+    RoundToNearest [-0.49999997, 3.4028235e38],
+    RoundToNegInf [-0.0, 3.4028235e38],
+    RoundToPosInf [-0.99999994, 3.4028235e38],
+}
+
+safe_range! { f64 => i8:
+    DefaultApprox [-128.99999999999997, 127.99999999999999],
+    RoundToZero [-128.99999999999997, 127.99999999999999],
+    RoundToNearest [-128.49999999999997, 127.49999999999999],
+    RoundToNegInf [-128.0, 127.99999999999999],
+    RoundToPosInf [-128.99999999999997, 127.0],
+}
+
+safe_range! { f64 => i16:
+    DefaultApprox [-32768.99999999999, 32767.999999999996],
+    RoundToZero [-32768.99999999999, 32767.999999999996],
+    RoundToNearest [-32768.49999999999, 32767.499999999996],
+    RoundToNegInf [-32768.0, 32767.999999999996],
+    RoundToPosInf [-32768.99999999999, 32767.0],
+}
+
+safe_range! { f64 => i32:
+    DefaultApprox [-2147483648.9999995, 2147483647.9999998],
+    RoundToZero [-2147483648.9999995, 2147483647.9999998],
+    RoundToNearest [-2147483648.4999995, 2147483647.4999998],
+    RoundToNegInf [-2147483648.0, 2147483647.9999998],
+    RoundToPosInf [-2147483648.9999995, 2147483647.0],
+}
+
+safe_range! { f64 => i64:
+    DefaultApprox [-9.223372036854776e18, 9.223372036854775e18],
+    RoundToZero [-9.223372036854776e18, 9.223372036854775e18],
+    RoundToNearest [-9.223372036854776e18, 9.223372036854775e18],
+    RoundToNegInf [-9.223372036854776e18, 9.223372036854775e18],
+    RoundToPosInf [-9.223372036854776e18, 9.223372036854775e18],
+}
+
+safe_range! { f64 => u8:
+    DefaultApprox [-0.9999999999999999, 255.99999999999997],
+    RoundToZero [-0.9999999999999999, 255.99999999999997],
+    RoundToNearest [-0.49999999999999994, 255.49999999999997],
+    RoundToNegInf [-0.0, 255.99999999999997],
+    RoundToPosInf [-0.9999999999999999, 255.0],
+}
+
+safe_range! { f64 => u16:
+    DefaultApprox [-0.9999999999999999, 65535.99999999999],
+    RoundToZero [-0.9999999999999999, 65535.99999999999],
+    RoundToNearest [-0.49999999999999994, 65535.49999999999],
+    RoundToNegInf [-0.0, 65535.99999999999],
+    RoundToPosInf [-0.9999999999999999, 65535.0],
+}
+
+safe_range! { f64 => u32:
+    DefaultApprox [-0.9999999999999999, 4294967295.9999995],
+    RoundToZero [-0.9999999999999999, 4294967295.9999995],
+    RoundToNearest [-0.49999999999999994, 4294967295.4999995],
+    RoundToNegInf [-0.0, 4294967295.9999995],
+    RoundToPosInf [-0.9999999999999999, 4294967295.0],
+}
+
+safe_range! { f64 => u64:
+    DefaultApprox [-0.0, 1.844674407370955e19],
+    RoundToZero [-0.0, 1.844674407370955e19],
+    RoundToNearest [-0.49999999999999994, 1.844674407370955e19],
+    RoundToNegInf [-0.0, 1.844674407370955e19],
+    RoundToPosInf [-0.9999999999999999, 1.844674407370955e19],
+}
+
+#[cfg(target_pointer_width = "32")]
+safe_range! { f64 => isize:
+    DefaultApprox [-2147483648.9999995, 2147483647.9999998],
+    RoundToZero [-2147483648.9999995, 2147483647.9999998],
+    RoundToNearest [-2147483648.4999995, 2147483647.4999998],
+    RoundToNegInf [-2147483648.0, 2147483647.9999998],
+    RoundToPosInf [-2147483648.9999995, 2147483647.0],
+}
+
 #[cfg(target_pointer_width = "64")]
-/// Minimum `f32` that can be represented in an `isize`.
-#[doc(hidden)]
-pub const MIN_F32_ISIZE: f32 = MIN_F32_I64;
+safe_range! { f64 => isize:
+    DefaultApprox [-9.223372036854776e18, 9.223372036854775e18],
+    RoundToZero [-9.223372036854776e18, 9.223372036854775e18],
+    RoundToNearest [-9.223372036854776e18, 9.223372036854775e18],
+    RoundToNegInf [-9.223372036854776e18, 9.223372036854775e18],
+    RoundToPosInf [-9.223372036854776e18, 9.223372036854775e18],
+}
+
+#[cfg(target_pointer_width = "32")]
+safe_range! { f64 => usize:
+    DefaultApprox [-0.9999999999999999, 4294967295.9999995],
+    RoundToZero [-0.9999999999999999, 4294967295.9999995],
+    RoundToNearest [-0.49999999999999994, 4294967295.4999995],
+    RoundToNegInf [-0.0, 4294967295.9999995],
+    RoundToPosInf [-0.9999999999999999, 4294967295.0],
+}
+
 #[cfg(target_pointer_width = "64")]
-/// Maximum `f32` that can be represented in a `usize`.
-#[doc(hidden)]
-pub const MAX_F32_USIZE: f32 = MAX_F32_U64;
-
-/// Maximum `f64` that can be represented in an `i64`.
-#[doc(hidden)]
-pub const MAX_F64_I64: f64 = 9.223372036854775e18;
-/// Minimum `f32` that can be represented in an `i64`.
-#[doc(hidden)]
-pub const MIN_F64_I64: f64 = -9.223372036854776e18;
-/// Maximum `f64` that can be represented in an `u64`.
-#[doc(hidden)]
-pub const MAX_F64_U64: f64 = 1.844674407370955e19;
+safe_range! { f64 => usize:
+    DefaultApprox [-0.0, 1.844674407370955e19],
+    RoundToZero [-0.0, 1.844674407370955e19],
+    RoundToNearest [-0.49999999999999994, 1.844674407370955e19],
+    RoundToNegInf [-0.0, 1.844674407370955e19],
+    RoundToPosInf [-0.9999999999999999, 1.844674407370955e19],
+}
+
+safe_range! { f64 => i128:
+    DefaultApprox [-1.7014118346046923e38, 1.7014118346046921e38],
+    RoundToZero [-1.7014118346046923e38, 1.7014118346046921e38],
+    RoundToNearest [-1.7014118346046923e38, 1.7014118346046921e38],
+    RoundToNegInf [-1.7014118346046923e38, 1.7014118346046921e38],
+    RoundToPosInf [-1.7014118346046923e38, 1.7014118346046921e38],
+}
+
+safe_range! { f64 => u128:
+    DefaultApprox [-0.9999999999999999, 3.4028236692093843e38],
+    RoundToZero [-0.9999999999999999, 3.4028236692093843e38],
+    RoundToNearest [-0.49999999999999994, 3.4028236692093843e38],
+    RoundToNegInf [-0.0, 3.4028236692093843e38],
+    RoundToPosInf [-0.9999999999999999, 3.4028236692093843e38],
+}
@@ -22,6 +22,125 @@ item_for_each! {
     }
 }
 
+/// This trait describes the range of values that a conversion from `Src` can
+/// produce.
+///
+/// For integer types, this is simply the type's `MIN` and `MAX`. For floating
+/// point types converted from integers, it is the range of integers that can
+/// be represented exactly.
+///
+/// This is used by the `errors::WithContext` extension trait.
+pub trait ConvRange<Src> {
+    /// Returns the smallest value a conversion from `Src` can produce.
+    fn range_min() -> Self;
+
+    /// Returns the largest value a conversion from `Src` can produce.
+    fn range_max() -> Self;
+}
+
+item_for_each! {
+    (i8), (i16), (i32), (i64), (u8), (u16), (u32), (u64), (isize), (usize) => {
+        ($ity:ident) => {
+            impl<Src> ConvRange<Src> for $ity {
+                #[inline] fn range_min() -> Self { $ity::MIN }
+                #[inline] fn range_max() -> Self { $ity::MAX }
+            }
+        };
+    }
+}
+
+macro_rules! float_conv_range {
+    ($fty:ident, [+- $exact:expr]; $($ity:ident),*) => {
+        $(
+            impl ConvRange<$ity> for $fty {
+                #[inline] fn range_min() -> Self { -$exact }
+                #[inline] fn range_max() -> Self { $exact }
+            }
+        )*
+
+        impl ConvRange<f32> for $fty {
+            #[inline] fn range_min() -> Self { $fty::MIN }
+            #[inline] fn range_max() -> Self { $fty::MAX }
+        }
+
+        impl ConvRange<f64> for $fty {
+            #[inline] fn range_min() -> Self { $fty::MIN }
+            #[inline] fn range_max() -> Self { $fty::MAX }
+        }
+    };
+}
+
+float_conv_range! { f32, [+- 16_777_216.0]; i8, i16, i32, i64, u8, u16, u32, u64, isize, usize }
+float_conv_range! { f64, [+- 9_007_199_254_740_992.0]; i8, i16, i32, i64, u8, u16, u32, u64, isize, usize }
+
+/// This trait describes the `ValueFrom<Src>` conversion into a type, so that
+/// generic code can inspect it without attempting a conversion.
+///
+/// It is implemented for the builtin numeric conversions. The values that
+/// convert successfully are exactly those from `SRC_MIN` to `SRC_MAX`,
+/// inclusive. Note that some of these conversions depend on the target's
+/// pointer width.
+///
+/// # Examples
+///
+/// ```
+/// use conv2::misc::ConversionInfo;
+///
+/// assert!(<i64 as ConversionInfo<u32>>::LOSSLESS);
+///
+/// assert!(!<u8 as ConversionInfo<i32>>::LOSSLESS);
+/// assert!(<u8 as ConversionInfo<i32>>::CAN_NEG_OVERFLOW);
+/// assert_eq!(<u8 as ConversionInfo<i32>>::SRC_MIN, 0);
+/// assert_eq!(<u8 as ConversionInfo<i32>>::SRC_MAX, 255);
+///
+/// assert!(!<f32 as ConversionInfo<u64>>::CAN_NEG_OVERFLOW);
+/// assert_eq!(<f32 as ConversionInfo<u64>>::SRC_MAX, 16_777_216);
+/// ```
+pub trait ConversionInfo<Src>: crate::ValueFrom<Src> {
+    /// Whether the conversion can fail because the value is too negative.
+    const CAN_NEG_OVERFLOW: bool;
+
+    /// Whether the conversion can fail because the value is too positive.
+    const CAN_POS_OVERFLOW: bool;
+
+    /// Whether every `Src` value converts successfully.
+    const LOSSLESS: bool = !Self::CAN_NEG_OVERFLOW && !Self::CAN_POS_OVERFLOW;
+
+    /// The smallest `Src` value that converts successfully.
+    const SRC_MIN: Src;
+
+    /// The largest `Src` value that converts successfully.
+    const SRC_MAX: Src;
+}
+
+item_for_each! {
+    (i8), (i16), (i32), (i64), (u8), (u16), (u32), (u64), (isize), (usize) => {
+        ($ity:ident) => {
+            impl ConversionInfo<$ity> for $ity {
+                const CAN_NEG_OVERFLOW: bool = false;
+                const CAN_POS_OVERFLOW: bool = false;
+                const SRC_MIN: $ity = $ity::MIN;
+                const SRC_MAX: $ity = $ity::MAX;
+            }
+        };
+    }
+}
+
+macro_rules! float_conversion_info {
+    ($($src:ident => $dst:ident),*) => {
+        $(
+            impl ConversionInfo<$src> for $dst {
+                const CAN_NEG_OVERFLOW: bool = false;
+                const CAN_POS_OVERFLOW: bool = false;
+                const SRC_MIN: $src = $src::NEG_INFINITY;
+                const SRC_MAX: $src = $src::INFINITY;
+            }
+        )*
+    };
+}
+
+float_conversion_info! { f32 => f32, f32 => f64, f64 => f64 }
+
 /// This trait indicates that a type has an "invalid" sentinel value.
 ///
 /// This is used by the `errors::UnwrapOrInvalid` extension trait.
@@ -0,0 +1,313 @@
+//! This module bridges `conv2` and the `num-traits` crate.
+//!
+//! The [`Num`] adaptor allows any type implementing `num-traits`' conversion
+//! traits (such as a big integer or fixed-point type from another crate) to
+//! be converted to and from the builtin numeric types using `ValueFrom` and
+//! `ApproxFrom`, with failures reported using this crate's error types:
+//!
+//! - `ValueFrom` conversions go through `ToPrimitive`/`FromPrimitive`, and
+//!   fail with `Unrepresentable` unless the value survives a round trip
+//!   exactly.
+//! - `ApproxFrom` conversions with the `DefaultApprox` and `RoundToZero`
+//!   schemes behave like `ToPrimitive`/`FromPrimitive`: fractional parts are
+//!   truncated, and values out of range fail.
+//!
+//! Out of range values are reported as `NegOverflow` or `PosOverflow`
+//! depending on their sign. NaN is reported as `Unrepresentable`. As with the
+//! builtin float conversions, a finite value too large for `f32` or `f64`
+//! overflows rather than becoming infinite. If `T` implements `Bounded`,
+//! `Num<T>` also implements `Saturated`.
+//!
+//! Going the other way, `Num<T>` implements `ToPrimitive`, `FromPrimitive`
+//! and `NumCast` using the `ValueFrom` conversions above, so generic code
+//! written against `num-traits` gets this crate's checks. Unlike the
+//! implementations for the builtin types, these return `None` instead of
+//! truncating fractional parts. [`cast`] is a drop-in replacement for
+//! `num_traits::cast` that uses this crate's checked conversions.
+//!
+//! This module requires the `num-traits` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! # use conv2::*;
+//! use conv2::num_compat::Num;
+//!
+//! assert_eq!(u8::value_from(Num(200u64)), Ok(200));
+//! assert_eq!(u8::value_from(Num(300u64)), Err(GeneralError::PosOverflow(300)));
+//! assert_eq!(u8::value_from(Num(1.5f64)), Err(GeneralError::Unrepresentable(1.5)));
+//! assert_eq!(Num::<u16>::value_from(-1i32), Err(GeneralError::NegOverflow(-1)));
+//!
+//! assert_eq!(Num(1e300f64).approx_as::<f32>(), Err(GeneralError::PosOverflow(1e300)));
+//!
+//! use num_traits::{NumCast, ToPrimitive};
+//! assert_eq!(Num(300u32).to_u8(), None);
+//! assert_eq!(<Num<u8> as NumCast>::from(200i64), Some(Num(200)));
+//! assert_eq!(<Num<u8> as NumCast>::from(2.5f64), None);
+//!
+//! assert_eq!(conv2::num_compat::cast::<f32, u8>(255.9), Some(255));
+//! assert_eq!(conv2::num_compat::cast::<f32, u8>(256.0), None);
+//! ```
+
+use std::fmt::{self, Debug, Display};
+
+use num_traits::{Bounded, FromPrimitive, NumCast, ToPrimitive, Zero};
+
+use crate::errors::GeneralError;
+use crate::misc::Saturated;
+use crate::{ApproxFrom, DefaultApprox, ValueFrom};
+
+/// Adapts a type implementing `num-traits`' conversion traits for use with
+/// `ValueFrom` and `ApproxFrom`.
+///
+/// See the [module documentation](self) for details.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Num<T>(pub T);
+
+impl<T> Num<T> {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Display for Num<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T> Saturated for Num<T>
+where
+    T: Bounded,
+{
+    #[inline]
+    fn saturated_max() -> Self {
+        Num(T::max_value())
+    }
+
+    #[inline]
+    fn saturated_min() -> Self {
+        Num(T::min_value())
+    }
+}
+
+/// Converts a value using this crate's `DefaultApprox` scheme.
+///
+/// This has the same signature as `num_traits::cast`, and returns `None`
+/// wherever the corresponding `ApproxFrom` conversion fails.
+#[inline]
+pub fn cast<Src, Dst>(src: Src) -> Option<Dst>
+where
+    Dst: ApproxFrom<Src, DefaultApprox>,
+{
+    Dst::approx_from(src).ok()
+}
+
+/// Compares the result of a round trip with the original value.
+///
+/// NaN is not equal to itself, but does survive a round trip.
+#[allow(clippy::eq_op)]
+fn same<T>(a: &T, b: &T) -> bool
+where
+    T: PartialEq,
+{
+    a == b || (a != a && b != b)
+}
+
+/// Builds the error for a value that `num-traits` could not convert.
+fn out_of_range<T>(v: T) -> GeneralError<T>
+where
+    T: PartialOrd + Zero,
+{
+    match v.partial_cmp(&T::zero()) {
+        Some(std::cmp::Ordering::Less) => GeneralError::NegOverflow(v),
+        Some(_) => GeneralError::PosOverflow(v),
+        None => GeneralError::Unrepresentable(v),
+    }
+}
+
+impl<T> NumCast for Num<T>
+where
+    T: ToPrimitive + FromPrimitive + PartialOrd + Zero + Debug + Clone,
+{
+    /// Converts `n` using `ValueFrom`, going through `i128`, `u128` or
+    /// `f64`.
+    #[inline]
+    fn from<N>(n: N) -> Option<Self>
+    where
+        N: ToPrimitive,
+    {
+        // `to_i128` and `to_u128` truncate, so fractional values, infinities
+        // and NaN go through `f64`. Integral values only do so when they are
+        // out of range for both, which for a float source is exact.
+        match n.to_f64() {
+            Some(f) if f.fract() != 0.0 => Num::value_from(f).ok(),
+            f => match (n.to_i128(), n.to_u128()) {
+                (Some(i), _) => Num::value_from(i).ok(),
+                (None, Some(u)) => Num::value_from(u).ok(),
+                (None, None) => f.and_then(|f| Num::value_from(f).ok()),
+            },
+        }
+    }
+}
+
+/// Returns `true` if `v` is an infinity, meaning that it converts to an
+/// infinite `f64` and back unchanged.
+fn is_infinite<T>(v: &T) -> bool
+where
+    T: ToPrimitive + FromPrimitive + PartialEq,
+{
+    v.to_f64()
+        .filter(|f| f.is_infinite())
+        .and_then(T::from_f64)
+        .map_or(false, |back| back == *v)
+}
+
+macro_rules! num_compat {
+    ($($prim:ident: $to:ident, $from:ident, $kind:ident;)*) => {
+        impl<T> ToPrimitive for Num<T>
+        where
+            T: ToPrimitive + FromPrimitive + PartialOrd + Zero + Debug + Clone,
+        {
+            $(
+                #[inline]
+                fn $to(&self) -> Option<$prim> {
+                    $prim::value_from(self.clone()).ok()
+                }
+            )*
+        }
+
+        impl<T> FromPrimitive for Num<T>
+        where
+            T: ToPrimitive + FromPrimitive,
+        {
+            $(
+                #[inline]
+                fn $from(n: $prim) -> Option<Self> {
+                    Num::value_from(n).ok()
+                }
+            )*
+        }
+
+        $(
+            impl<T> crate::ValueFrom<Num<T>> for $prim
+            where
+                T: ToPrimitive + FromPrimitive + PartialOrd + Zero + Debug,
+            {
+                type Err = GeneralError<T>;
+                #[inline]
+                fn value_from(src: Num<T>) -> Result<$prim, Self::Err> {
+                    let src = src.0;
+                    match src.$to() {
+                        Some(dst) if T::$from(dst).map_or(false, |back| same(&back, &src)) => {
+                            Ok(dst)
+                        }
+                        Some(_) => Err(GeneralError::Unrepresentable(src)),
+                        None => Err(out_of_range(src)),
+                    }
+                }
+            }
+
+            impl<T> crate::ValueFrom<$prim> for Num<T>
+            where
+                T: ToPrimitive + FromPrimitive,
+            {
+                type Err = GeneralError<$prim>;
+                #[inline]
+                fn value_from(src: $prim) -> Result<Num<T>, Self::Err> {
+                    match T::$from(src) {
+                        Some(dst) if dst.$to().map_or(false, |back| same(&back, &src)) => {
+                            Ok(Num(dst))
+                        }
+                        Some(_) => Err(GeneralError::Unrepresentable(src)),
+                        None => Err(out_of_range(src)),
+                    }
+                }
+            }
+
+            num_compat! { @approx $kind $prim: $to, $from; crate::DefaultApprox }
+            num_compat! { @approx $kind $prim: $to, $from; crate::RoundToZero }
+        )*
+    };
+
+    (@approx int $prim:ident: $to:ident, $from:ident; $scheme:ty) => {
+        impl<T> crate::ApproxFrom<Num<T>, $scheme> for $prim
+        where
+            T: ToPrimitive + PartialOrd + Zero + Debug,
+        {
+            type Err = GeneralError<T>;
+            #[inline]
+            fn approx_from(src: Num<T>) -> Result<$prim, Self::Err> {
+                let src = src.0;
+                src.$to().ok_or_else(|| out_of_range(src))
+            }
+        }
+
+        impl<T> crate::ApproxFrom<$prim, $scheme> for Num<T>
+        where
+            T: FromPrimitive,
+        {
+            type Err = GeneralError<$prim>;
+            #[inline]
+            fn approx_from(src: $prim) -> Result<Num<T>, Self::Err> {
+                T::$from(src).map(Num).ok_or_else(|| out_of_range(src))
+            }
+        }
+    };
+
+    // `num-traits` turns finite floats that are out of range into infinities;
+    // these report them as overflows instead.
+    (@approx float $prim:ident: $to:ident, $from:ident; $scheme:ty) => {
+        impl<T> crate::ApproxFrom<Num<T>, $scheme> for $prim
+        where
+            T: ToPrimitive + FromPrimitive + PartialOrd + Zero + Debug,
+        {
+            type Err = GeneralError<T>;
+            #[inline]
+            fn approx_from(src: Num<T>) -> Result<$prim, Self::Err> {
+                let src = src.0;
+                match src.$to() {
+                    Some(dst) if dst.is_infinite() && !is_infinite(&src) => Err(out_of_range(src)),
+                    Some(dst) => Ok(dst),
+                    None => Err(out_of_range(src)),
+                }
+            }
+        }
+
+        impl<T> crate::ApproxFrom<$prim, $scheme> for Num<T>
+        where
+            T: ToPrimitive + FromPrimitive + PartialEq,
+        {
+            type Err = GeneralError<$prim>;
+            #[inline]
+            fn approx_from(src: $prim) -> Result<Num<T>, Self::Err> {
+                match T::$from(src) {
+                    Some(dst) if src.is_finite() && is_infinite(&dst) => Err(out_of_range(src)),
+                    Some(dst) => Ok(Num(dst)),
+                    None => Err(out_of_range(src)),
+                }
+            }
+        }
+    };
+}
+
+num_compat! {
+    i8: to_i8, from_i8, int;
+    i16: to_i16, from_i16, int;
+    i32: to_i32, from_i32, int;
+    i64: to_i64, from_i64, int;
+    isize: to_isize, from_isize, int;
+    i128: to_i128, from_i128, int;
+    u8: to_u8, from_u8, int;
+    u16: to_u16, from_u16, int;
+    u32: to_u32, from_u32, int;
+    u64: to_u64, from_u64, int;
+    usize: to_usize, from_usize, int;
+    u128: to_u128, from_u128, int;
+    f32: to_f32, from_f32, float;
+    f64: to_f64, from_f64, float;
+}
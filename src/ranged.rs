@@ -0,0 +1,388 @@
+//! This module provides integer types whose range is part of the type, such
+//! as [`RangedI32<MIN, MAX>`](RangedI32).
+//!
+//! There is one type for each builtin integer type, from [`RangedI8`] to
+//! [`RangedUsize`]. Unlike [`Bounded`], the bounds have the same type as the
+//! value. Both families implement the [`Ranged`] trait, and the compiler
+//! checks how the ranges of two such types relate:
+//!
+//! - `ValueFrom` converts any builtin integer into a ranged type, failing with
+//!   a `RangeError` outside its bounds. `ApproxFrom` does the same for every
+//!   builtin number, rounding first; floats fail with a `FloatError`.
+//! - `ValueFrom` converts a ranged type back into any builtin integer, with
+//!   the same error type as converting its underlying type. For instance, a
+//!   `RangedU8` converts into a `u16` with `NoError`, but into an `i8` with
+//!   `PosOverflow`, even if its range fits in `i8`.
+//! - `ValueFrom` converts between ranged types with different underlying
+//!   types, and between ranged types and `Bounded`, with `NoError`. The
+//!   destination range must contain the source range; otherwise, the
+//!   conversion fails to *compile*.
+//! - [`Ranged::widen`] does the same between any two ranged types, including
+//!   those with the same underlying type.
+//! - [`Ranged::narrow`] converts into any ranged type, failing with a
+//!   `RangeError` if the value is outside its bounds.
+//! - `Saturated` returns the bounds, so `unwrap_or_saturate` clamps to them.
+//!
+//! `ValueFrom` cannot be implemented between two ranged types with the same
+//! underlying type, since it would overlap with the implementation that
+//! converts every type into itself; use `widen` instead.
+//!
+//! The bounds must satisfy `MIN <= MAX`; otherwise, any use of the type fails
+//! to compile.
+//!
+//! # Examples
+//!
+//! ```
+//! # use conv2::*;
+//! use conv2::bounded::Bounded;
+//! use conv2::ranged::{Ranged, RangedI32, RangedI64, RangedU8};
+//!
+//! type Month = RangedU8<1, 12>;
+//! type DayOfYear = RangedI32<1, 366>;
+//!
+//! let m = Month::value_from(7i64).unwrap();
+//! assert_eq!(m.get(), 7);
+//! assert_eq!(Month::value_from(13u32), Err(RangeError::PosOverflow(13)));
+//! assert_eq!(Month::value_from(-1i8).unwrap_or_saturate().get(), 1);
+//! assert_eq!(6.7f64.approx_as::<Month>().map(Month::get), Ok(6));
+//!
+//! // Into builtin types containing `u8`, conversions cannot fail.
+//! let x: Result<u16, NoError> = m.value_into();
+//! assert_eq!(x, Ok(7));
+//!
+//! // 1..=12 nests in 1..=366, so these are checked at compile time.
+//! let d: Result<DayOfYear, NoError> = m.value_into();
+//! assert_eq!(d.map(DayOfYear::get), Ok(7));
+//! let d: Bounded<i16, 0, 1000> = m.value_into().unwrap_ok();
+//! assert_eq!(d.get(), 7);
+//! let d: RangedU8<0, 99> = m.widen();
+//! assert_eq!(d.get(), 7);
+//!
+//! // The other way may fail.
+//! let d = DayOfYear::new(100).unwrap();
+//! assert_eq!(d.narrow::<Month>(), Err(RangeError::PosOverflow(d)));
+//! assert_eq!(d.narrow::<RangedI64<0, 99>>(), Err(RangeError::PosOverflow(d)));
+//! ```
+//!
+//! Converting into a range that does not contain the source's fails to
+//! compile. Since the ranges are const parameters, which trait bounds cannot
+//! compare, this check runs when the conversion is instantiated: `cargo
+//! build` and `cargo test` report it, but `cargo check` does not, and generic
+//! code only fails once it is used with such a pair of types. Where the
+//! ranges may not nest, use [`Ranged::narrow`], which checks the value at run
+//! time instead:
+//!
+//! ```compile_fail
+//! # use conv2::*;
+//! use conv2::ranged::{RangedI32, RangedU8};
+//!
+//! let m = RangedU8::<1, 12>::new(7).unwrap();
+//! let d: Result<RangedI32<2, 366>, NoError> = m.value_into();
+//! ```
+//!
+//! ```compile_fail
+//! use conv2::ranged::{Ranged, RangedU8};
+//!
+//! let m = RangedU8::<1, 12>::new(7).unwrap();
+//! let d: RangedU8<2, 12> = m.widen();
+//! ```
+
+use std::fmt;
+
+use crate::bounded::Bounded;
+use crate::errors::{FloatError, NoError, RangeError};
+use crate::misc::Saturated;
+use crate::{ApproxFrom, ApproxScheme, ValueFrom};
+
+/// The operations shared by every ranged integer type, including
+/// [`Bounded`].
+///
+/// This trait cannot be implemented outside this crate.
+pub trait Ranged: Copy + sealed::Sealed {
+    /// The underlying integer type.
+    type Repr: Copy;
+
+    /// The smallest allowed value, as an `i128`.
+    const LOWER: i128;
+
+    /// The largest allowed value, as an `i128`.
+    const UPPER: i128;
+
+    /// Returns the value.
+    fn get(self) -> Self::Repr;
+
+    /// Converts into a ranged type whose range contains this type's range.
+    ///
+    /// This fails to compile if `Dst`'s range does not contain this type's.
+    #[inline]
+    fn widen<Dst>(self) -> Dst
+    where
+        Dst: Ranged,
+    {
+        #[allow(clippy::let_unit_value)]
+        let () = Nested::<Self, Dst>::CHECK;
+        Dst::from_i128_unchecked(self.to_i128(sealed::Token), sealed::Token)
+    }
+
+    /// Converts into any ranged type, failing if the value is outside its
+    /// bounds.
+    #[inline]
+    fn narrow<Dst>(self) -> Result<Dst, RangeError<Self>>
+    where
+        Dst: Ranged,
+    {
+        check(self.to_i128(sealed::Token), self)
+    }
+}
+
+pub(crate) mod sealed {
+    /// Restricts the methods below to this crate.
+    ///
+    /// Methods of a supertrait can be called through a `T: Ranged` bound, so
+    /// sealing the trait alone does not hide them.
+    #[derive(Copy, Clone)]
+    pub struct Token;
+
+    pub trait Sealed: Copy {
+        /// Returns the value as an `i128`.
+        fn to_i128(self, _: Token) -> i128;
+
+        /// Builds a value that is known to be within the bounds.
+        fn from_i128_unchecked(value: i128, _: Token) -> Self;
+    }
+}
+
+/// Checks a converted value against `Dst`'s bounds, reporting a failure with
+/// the original value `src`.
+#[inline]
+pub(crate) fn check<Dst, Src, E>(value: i128, src: Src) -> Result<Dst, E>
+where
+    Dst: Ranged,
+    E: From<RangeError<Src>>,
+{
+    if value < Dst::LOWER {
+        Err(RangeError::NegOverflow(src).into())
+    } else if value > Dst::UPPER {
+        Err(RangeError::PosOverflow(src).into())
+    } else {
+        Ok(Dst::from_i128_unchecked(value, sealed::Token))
+    }
+}
+
+/// Returns the largest allowed value of `T`.
+#[inline]
+pub(crate) fn saturated_max<T: Ranged>() -> T {
+    T::from_i128_unchecked(T::UPPER, sealed::Token)
+}
+
+/// Returns the smallest allowed value of `T`.
+#[inline]
+pub(crate) fn saturated_min<T: Ranged>() -> T {
+    T::from_i128_unchecked(T::LOWER, sealed::Token)
+}
+
+/// Checks at compile time that `Src`'s range nests in `Dst`'s.
+struct Nested<Src, Dst>(Src, Dst);
+
+impl<Src, Dst> Nested<Src, Dst>
+where
+    Src: Ranged,
+    Dst: Ranged,
+{
+    const CHECK: () = assert!(
+        Dst::LOWER <= Src::LOWER && Src::UPPER <= Dst::UPPER,
+        "the destination range does not contain the source range"
+    );
+}
+
+macro_rules! ranged_cross {
+    ($name:ident($repr:ident): $($other:ident($orepr:ident)),*) => {
+        $(
+            impl<const MIN: $repr, const MAX: $repr, const A: $orepr, const B: $orepr>
+                ValueFrom<$other<A, B>> for $name<MIN, MAX>
+            {
+                type Err = NoError;
+                #[inline]
+                fn value_from(src: $other<A, B>) -> Result<Self, Self::Err> {
+                    Ok(src.widen())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! ranged_bounded {
+    ($name:ident($repr:ident): $($brepr:ident),*) => {
+        $(
+            impl<const MIN: $repr, const MAX: $repr, const A: i128, const B: i128>
+                ValueFrom<Bounded<$brepr, A, B>> for $name<MIN, MAX>
+            {
+                type Err = NoError;
+                #[inline]
+                fn value_from(src: Bounded<$brepr, A, B>) -> Result<Self, Self::Err> {
+                    Ok(src.widen())
+                }
+            }
+
+            impl<const MIN: $repr, const MAX: $repr, const A: i128, const B: i128>
+                ValueFrom<$name<MIN, MAX>> for Bounded<$brepr, A, B>
+            {
+                type Err = NoError;
+                #[inline]
+                fn value_from(src: $name<MIN, MAX>) -> Result<Self, Self::Err> {
+                    Ok(src.widen())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! ranged {
+    ($($name:ident($repr:ident)),*) => {
+        $(
+            #[doc = concat!("A `", stringify!($repr), "` restricted to `MIN..=MAX`.")]
+            ///
+            /// See the [module documentation](self) for details.
+            #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            pub struct $name<const MIN: $repr, const MAX: $repr>($repr);
+
+            impl<const MIN: $repr, const MAX: $repr> $name<MIN, MAX> {
+                /// The smallest allowed value.
+                pub const MIN: $repr = {
+                    assert!(MIN <= MAX, "invalid bounds");
+                    MIN
+                };
+
+                /// The largest allowed value.
+                pub const MAX: $repr = {
+                    let _ = Self::MIN;
+                    MAX
+                };
+
+                /// Returns the value if it is within the bounds, or `None`.
+                #[inline]
+                pub const fn new(value: $repr) -> Option<Self> {
+                    if value < Self::MIN || value > Self::MAX {
+                        None
+                    } else {
+                        Some($name(value))
+                    }
+                }
+
+                /// Returns the value.
+                #[inline]
+                pub const fn get(self) -> $repr {
+                    self.0
+                }
+            }
+
+            impl<const MIN: $repr, const MAX: $repr> sealed::Sealed for $name<MIN, MAX> {
+                #[inline]
+                fn to_i128(self, _: sealed::Token) -> i128 {
+                    self.0 as i128
+                }
+
+                #[inline]
+                fn from_i128_unchecked(value: i128, _: sealed::Token) -> Self {
+                    $name(value as $repr)
+                }
+            }
+
+            impl<const MIN: $repr, const MAX: $repr> Ranged for $name<MIN, MAX> {
+                type Repr = $repr;
+                const LOWER: i128 = Self::MIN as i128;
+                const UPPER: i128 = Self::MAX as i128;
+
+                #[inline]
+                fn get(self) -> $repr {
+                    self.0
+                }
+            }
+
+            impl<const MIN: $repr, const MAX: $repr> fmt::Display for $name<MIN, MAX> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Display::fmt(&self.0, f)
+                }
+            }
+
+            impl<const MIN: $repr, const MAX: $repr> Saturated for $name<MIN, MAX> {
+                #[inline]
+                fn saturated_max() -> Self {
+                    saturated_max()
+                }
+
+                #[inline]
+                fn saturated_min() -> Self {
+                    saturated_min()
+                }
+            }
+
+            ranged_prim! { $name($repr); RangeError: i8, i16, i32, i64, isize, u8, u16, u32, u64, usize }
+            ranged_prim! { $name($repr); FloatError: f32, f64 }
+            ranged_bounded! { $name($repr): i8, i16, i32, i64, isize, u8, u16, u32, u64, usize }
+        )*
+    };
+}
+
+macro_rules! ranged_prim {
+    ($name:ident($repr:ident); RangeError: $($prim:ident),*) => {
+        $(
+            impl<const MIN: $repr, const MAX: $repr> ValueFrom<$prim> for $name<MIN, MAX> {
+                type Err = RangeError<$prim>;
+                #[inline]
+                fn value_from(src: $prim) -> Result<Self, Self::Err> {
+                    let value = $repr::value_from(src).map_err(RangeError::from)?;
+                    check(value as i128, src)
+                }
+            }
+
+            impl<const MIN: $repr, const MAX: $repr> ValueFrom<$name<MIN, MAX>> for $prim {
+                type Err = <$prim as ValueFrom<$repr>>::Err;
+                #[inline]
+                fn value_from(src: $name<MIN, MAX>) -> Result<Self, Self::Err> {
+                    $prim::value_from(src.0)
+                }
+            }
+
+            ranged_prim! { @approx $name($repr); RangeError: $prim }
+        )*
+    };
+
+    ($name:ident($repr:ident); FloatError: $($prim:ident),*) => {
+        $(
+            ranged_prim! { @approx $name($repr); FloatError: $prim }
+        )*
+    };
+
+    (@approx $name:ident($repr:ident); $err:ident: $prim:ident) => {
+        impl<Scheme, const MIN: $repr, const MAX: $repr> ApproxFrom<$prim, Scheme>
+            for $name<MIN, MAX>
+        where
+            Scheme: ApproxScheme,
+            $repr: ApproxFrom<$prim, Scheme>,
+            $err<$prim>: From<<$repr as ApproxFrom<$prim, Scheme>>::Err>,
+        {
+            type Err = $err<$prim>;
+            #[inline]
+            fn approx_from(src: $prim) -> Result<Self, Self::Err> {
+                let value = $repr::approx_from(src)?;
+                check(value as i128, src)
+            }
+        }
+    };
+}
+
+ranged! {
+    RangedI8(i8), RangedI16(i16), RangedI32(i32), RangedI64(i64), RangedIsize(isize),
+    RangedU8(u8), RangedU16(u16), RangedU32(u32), RangedU64(u64), RangedUsize(usize)
+}
+ranged_cross! { RangedI8(i8): RangedI16(i16), RangedI32(i32), RangedI64(i64), RangedIsize(isize), RangedU8(u8), RangedU16(u16), RangedU32(u32), RangedU64(u64), RangedUsize(usize) }
+ranged_cross! { RangedI16(i16): RangedI8(i8), RangedI32(i32), RangedI64(i64), RangedIsize(isize), RangedU8(u8), RangedU16(u16), RangedU32(u32), RangedU64(u64), RangedUsize(usize) }
+ranged_cross! { RangedI32(i32): RangedI8(i8), RangedI16(i16), RangedI64(i64), RangedIsize(isize), RangedU8(u8), RangedU16(u16), RangedU32(u32), RangedU64(u64), RangedUsize(usize) }
+ranged_cross! { RangedI64(i64): RangedI8(i8), RangedI16(i16), RangedI32(i32), RangedIsize(isize), RangedU8(u8), RangedU16(u16), RangedU32(u32), RangedU64(u64), RangedUsize(usize) }
+ranged_cross! { RangedIsize(isize): RangedI8(i8), RangedI16(i16), RangedI32(i32), RangedI64(i64), RangedU8(u8), RangedU16(u16), RangedU32(u32), RangedU64(u64), RangedUsize(usize) }
+ranged_cross! { RangedU8(u8): RangedI8(i8), RangedI16(i16), RangedI32(i32), RangedI64(i64), RangedIsize(isize), RangedU16(u16), RangedU32(u32), RangedU64(u64), RangedUsize(usize) }
+ranged_cross! { RangedU16(u16): RangedI8(i8), RangedI16(i16), RangedI32(i32), RangedI64(i64), RangedIsize(isize), RangedU8(u8), RangedU32(u32), RangedU64(u64), RangedUsize(usize) }
+ranged_cross! { RangedU32(u32): RangedI8(i8), RangedI16(i16), RangedI32(i32), RangedI64(i64), RangedIsize(isize), RangedU8(u8), RangedU16(u16), RangedU64(u64), RangedUsize(usize) }
+ranged_cross! { RangedU64(u64): RangedI8(i8), RangedI16(i16), RangedI32(i32), RangedI64(i64), RangedIsize(isize), RangedU8(u8), RangedU16(u16), RangedU32(u32), RangedUsize(usize) }
+ranged_cross! { RangedUsize(usize): RangedI8(i8), RangedI16(i16), RangedI32(i32), RangedI64(i64), RangedIsize(isize), RangedU8(u8), RangedU16(u16), RangedU32(u32), RangedU64(u64) }
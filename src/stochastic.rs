@@ -0,0 +1,228 @@
+//! This module provides stochastic rounding, where a value is rounded up or
+//! down at random.
+//!
+//! A value is rounded up with probability equal to its distance from the
+//! value below, as a fraction of the gap between the two. For example, `2.25`
+//! becomes `3` a quarter of the time, and `2` otherwise. The result is
+//! unbiased: on average, it equals the input. This is useful for quantization,
+//! where the errors from always rounding the same way would accumulate.
+//!
+//! Stochastic rounding cannot be an [`ApproxScheme`](crate::ApproxScheme),
+//! since it needs a source of randomness. Instead, [`StochasticFrom`] and
+//! [`StochasticInto`] take one as an argument, which must implement
+//! [`RandomSource`]. The [`SplitMix64`] generator is provided for this;
+//! seeding it with a fixed value makes the results reproducible.
+//!
+//! These conversions are implemented for float → integer and `f64` → `f32`.
+//! Values are accepted only if rounding in *either* direction gives a
+//! representable result, so whether a conversion fails never depends on the
+//! random values. For float → integer, this range is from
+//! `SafeRange<Src, Dst, RoundToNegInf>::MIN` to
+//! `SafeRange<Src, Dst, RoundToPosInf>::MAX`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use conv2::*;
+//! use conv2::stochastic::{SplitMix64, StochasticFrom, StochasticInto};
+//!
+//! let mut rng = SplitMix64::new(42);
+//!
+//! let x: i32 = 2.25f64.approx_with_rng(&mut rng).unwrap();
+//! assert!(x == 2 || x == 3);
+//!
+//! // Exact values are never rounded.
+//! assert_eq!(7.0f32.approx_with_rng(&mut rng), Ok(7u8));
+//!
+//! // On average, the result equals the input.
+//! let sum: i32 = (0..10_000)
+//!     .map(|_| i32::stochastic_from(0.25f64, &mut rng).unwrap())
+//!     .sum();
+//! assert!((2_300..2_700).contains(&sum));
+//!
+//! assert_eq!(
+//!     u8::stochastic_from(255.5f64, &mut rng),
+//!     Err(FloatError::PosOverflow(255.5))
+//! );
+//! ```
+
+use crate::errors::{FloatError, NoError, RangeError};
+use crate::{RoundToNegInf, RoundToPosInf, SafeRange};
+
+/// A source of uniformly distributed random numbers.
+pub trait RandomSource {
+    /// Returns the next random value, with every bit uniformly distributed.
+    fn next_u64(&mut self) -> u64;
+}
+
+impl<R> RandomSource for &mut R
+where
+    R: RandomSource + ?Sized,
+{
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+}
+
+/// A small, fast, seedable pseudo-random number generator.
+///
+/// This is Sebastiano Vigna's SplitMix64. It is not suitable for
+/// cryptographic use, but is more than good enough for rounding.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Creates a generator from a seed. The same seed always produces the
+    /// same sequence.
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+}
+
+impl RandomSource for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// This trait is used to perform a stochastically rounded conversion; see
+/// the [module documentation](self).
+pub trait StochasticFrom<Src>: Sized {
+    /// The error type produced by a failed conversion.
+    type Err: std::error::Error;
+
+    /// Converts the given value, rounding up or down at random.
+    fn stochastic_from<R>(src: Src, rng: &mut R) -> Result<Self, Self::Err>
+    where
+        R: RandomSource + ?Sized;
+}
+
+impl<Src> StochasticFrom<Src> for Src {
+    type Err = NoError;
+    fn stochastic_from<R>(src: Src, _: &mut R) -> Result<Self, Self::Err>
+    where
+        R: RandomSource + ?Sized,
+    {
+        Ok(src)
+    }
+}
+
+/// This is the dual of `StochasticFrom`; see that trait for information.
+pub trait StochasticInto<Dst> {
+    /// The error type produced by a failed conversion.
+    type Err: std::error::Error;
+
+    /// Converts the subject, rounding up or down at random.
+    fn approx_with_rng<R>(self, rng: &mut R) -> Result<Dst, Self::Err>
+    where
+        R: RandomSource + ?Sized;
+}
+
+impl<Src, Dst> StochasticInto<Dst> for Src
+where
+    Dst: StochasticFrom<Src>,
+{
+    type Err = Dst::Err;
+    fn approx_with_rng<R>(self, rng: &mut R) -> Result<Dst, Self::Err>
+    where
+        R: RandomSource + ?Sized,
+    {
+        Dst::stochastic_from(self, rng)
+    }
+}
+
+/// Decides whether to round up a value that lies `frac` of the way from the
+/// value below to the value above.
+///
+/// No random value is drawn for exact values.
+fn round_up<R>(rng: &mut R, frac: f64) -> bool
+where
+    R: RandomSource + ?Sized,
+{
+    // A uniform value in [0, 1), with 53 bits of precision.
+    frac > 0.0 && (rng.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64) < frac
+}
+
+macro_rules! stochastic_float2int {
+    ($src:ident => $($dst:ident),*) => {
+        $(
+            impl StochasticFrom<$src> for $dst {
+                type Err = FloatError<$src>;
+                #[inline]
+                fn stochastic_from<R>(src: $src, rng: &mut R) -> Result<$dst, Self::Err>
+                where
+                    R: RandomSource + ?Sized,
+                {
+                    if src.is_nan() {
+                        return Err(FloatError::NotANumber(src));
+                    }
+                    if src < SafeRange::<$src, $dst, RoundToNegInf>::MIN {
+                        return Err(FloatError::NegOverflow(src));
+                    }
+                    if src > SafeRange::<$src, $dst, RoundToPosInf>::MAX {
+                        return Err(FloatError::PosOverflow(src));
+                    }
+                    // Both `src - floor` and `floor + 1.0` are exact: a value
+                    // with a fractional part is small enough for that.
+                    let floor = src.floor();
+                    let approx = if round_up(rng, (src - floor) as f64) {
+                        floor + 1.0
+                    } else {
+                        floor
+                    };
+                    Ok(unsafe { approx.to_int_unchecked::<$dst>() })
+                }
+            }
+        )*
+    };
+}
+
+stochastic_float2int! { f32 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize }
+stochastic_float2int! { f64 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize }
+
+/// Returns the next `f32` towards positive infinity, for finite values.
+fn next_up(x: f32) -> f32 {
+    if x == 0.0 {
+        f32::from_bits(1)
+    } else if x > 0.0 {
+        f32::from_bits(x.to_bits() + 1)
+    } else {
+        f32::from_bits(x.to_bits() - 1)
+    }
+}
+
+impl StochasticFrom<f64> for f32 {
+    type Err = RangeError<f64>;
+    #[inline]
+    fn stochastic_from<R>(src: f64, rng: &mut R) -> Result<f32, Self::Err>
+    where
+        R: RandomSource + ?Sized,
+    {
+        // As with `ApproxFrom`, infinities and NaN pass through.
+        if !src.is_finite() {
+            return Ok(src as f32);
+        }
+        if src < f32::MIN as f64 {
+            return Err(RangeError::NegOverflow(src));
+        }
+        if src > f32::MAX as f64 {
+            return Err(RangeError::PosOverflow(src));
+        }
+        let nearest = src as f32;
+        let (lo, hi) = if (nearest as f64) < src {
+            (nearest, next_up(nearest))
+        } else if (nearest as f64) > src {
+            (-next_up(-nearest), nearest)
+        } else {
+            return Ok(nearest);
+        };
+        let frac = (src - lo as f64) / (hi as f64 - lo as f64);
+        Ok(if round_up(rng, frac) { hi } else { lo })
+    }
+}
@@ -0,0 +1,214 @@
+//! This module provides `proptest` strategies that generate values near the
+//! edges of conversions.
+//!
+//! Uniformly random inputs rarely land where a conversion switches between
+//! succeeding and failing. [`boundary_values::<Src, Dst>()`](boundary_values)
+//! instead generates `Src` values clustered around the bounds of the
+//! conversion into `Dst`, using the same limits as the conversions themselves
+//! (see [`SafeRange`] and [`ConversionInfo`]):
+//!
+//! - each bound, and its nearest neighbours on either side;
+//! - the source type's own extremes, and zero;
+//! - for floats, also NaN, ±∞, ±0, subnormals, and `±MIN_POSITIVE`.
+//!
+//! A small fraction of values are drawn from the whole source type.
+//!
+//! This module requires the `proptest` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use conv2::strategy::boundary_values;
+//! use conv2::*;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(x in boundary_values::<f64, u8>())| {
+//!     let in_range = (SafeRange::<f64, u8>::MIN..=SafeRange::<f64, u8>::MAX).contains(&x);
+//!     prop_assert_eq!(x.approx_as::<u8>().is_ok(), in_range);
+//! });
+//! ```
+
+use std::fmt::Debug;
+
+use proptest::prelude::*;
+use proptest::sample::select;
+
+use crate::misc::ConversionInfo;
+use crate::{DefaultApprox, RoundToNearest, RoundToNegInf, RoundToPosInf, RoundToZero, SafeRange};
+
+/// How many neighbours of each bound are generated, on either side.
+const NEIGHBOURS: u32 = 2;
+
+/// Conversions into `Self` whose bounds are known.
+///
+/// This is implemented for every builtin conversion with a `ValueFrom` or
+/// float → integer `ApproxFrom` implementation. It can be implemented for
+/// other types, so that [`boundary_values`] can be used with them.
+pub trait Boundaries<Src> {
+    /// Returns the `Src` values at which conversions into `Self` switch
+    /// between succeeding and failing.
+    ///
+    /// For the builtin conversions, these are the smallest and largest values
+    /// that convert successfully. Their neighbours are generated by
+    /// [`boundary_values`], so they need not be included.
+    fn boundaries() -> Vec<Src>;
+}
+
+/// Returns a strategy generating `Src` values near the bounds of conversions
+/// into `Dst`.
+///
+/// See the [module documentation](self) for details.
+pub fn boundary_values<Src, Dst>() -> BoxedStrategy<Src>
+where
+    Src: Edge + Arbitrary,
+    Dst: Boundaries<Src>,
+{
+    let mut edges = Vec::new();
+    for bound in Dst::boundaries().into_iter().chain(Src::specials()) {
+        edges.push(bound);
+        for n in 1..=NEIGHBOURS {
+            edges.push(bound.step(-(n as i32)));
+            edges.push(bound.step(n as i32));
+        }
+    }
+    prop_oneof![
+        9 => select(edges),
+        1 => any::<Src>(),
+    ]
+    .boxed()
+}
+
+/// Source types that [`boundary_values`] can generate.
+///
+/// This trait is implemented for the builtin numeric types, and cannot be
+/// implemented outside this crate.
+pub trait Edge: Copy + Debug + 'static + sealed::Sealed {
+    /// Returns values of this type that are interesting on their own.
+    #[doc(hidden)]
+    fn specials() -> Vec<Self>;
+
+    /// Returns the value `n` steps away, saturating at the ends of the type.
+    /// For floats, each step is to the adjacent representable value.
+    #[doc(hidden)]
+    fn step(self, n: i32) -> Self;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! int_edge {
+    ($($ity:ident),*) => {
+        $(
+            impl sealed::Sealed for $ity {}
+
+            impl Edge for $ity {
+                fn specials() -> Vec<Self> {
+                    vec![$ity::MIN, 0, $ity::MAX]
+                }
+
+                fn step(self, n: i32) -> Self {
+                    let n = n as i128;
+                    (self as i128 + n).clamp($ity::MIN as i128, $ity::MAX as i128) as $ity
+                }
+            }
+        )*
+    };
+}
+
+int_edge! { i8, i16, i32, i64, isize, u8, u16, u32, u64, usize }
+
+macro_rules! float_edge {
+    ($($fty:ident: $bits:ident),*) => {
+        $(
+            impl sealed::Sealed for $fty {}
+
+            impl Edge for $fty {
+                fn specials() -> Vec<Self> {
+                    let subnormal = $fty::from_bits(1);
+                    let max_subnormal = $fty::from_bits(<$fty>::MIN_POSITIVE.to_bits() - 1);
+                    vec![
+                        $fty::NAN,
+                        $fty::NEG_INFINITY,
+                        $fty::INFINITY,
+                        -0.0,
+                        0.0,
+                        -subnormal,
+                        subnormal,
+                        -max_subnormal,
+                        max_subnormal,
+                        -$fty::MIN_POSITIVE,
+                        $fty::MIN_POSITIVE,
+                        -1.0,
+                        1.0,
+                        $fty::MIN,
+                        $fty::MAX,
+                    ]
+                }
+
+                fn step(self, n: i32) -> Self {
+                    if self.is_nan() {
+                        return self;
+                    }
+                    // Map the floats onto integers in order, with `-0.0` just
+                    // below `0.0`, so that adjacent floats are adjacent keys.
+                    let to_key = |x: $fty| {
+                        let bits = x.to_bits() as $bits;
+                        if bits < 0 { !(bits & $bits::MAX) } else { bits }
+                    };
+                    let from_key = |key: $bits| {
+                        $fty::from_bits((if key < 0 { !key | $bits::MIN } else { key }) as _)
+                    };
+                    let key = (to_key(self) as i128 + n as i128).clamp(
+                        to_key($fty::NEG_INFINITY) as i128,
+                        to_key($fty::INFINITY) as i128,
+                    );
+                    from_key(key as $bits)
+                }
+            }
+        )*
+    };
+}
+
+float_edge! { f32: i32, f64: i64 }
+
+impl<Src, Dst> Boundaries<Src> for Dst
+where
+    Dst: ConversionInfo<Src>,
+{
+    fn boundaries() -> Vec<Src> {
+        vec![Dst::SRC_MIN, Dst::SRC_MAX]
+    }
+}
+
+impl Boundaries<f64> for f32 {
+    fn boundaries() -> Vec<f64> {
+        vec![f32::MIN as f64, f32::MAX as f64]
+    }
+}
+
+macro_rules! float_to_int_boundaries {
+    ($src:ident => $($dst:ident),*) => {
+        $(
+            impl Boundaries<$src> for $dst {
+                fn boundaries() -> Vec<$src> {
+                    vec![
+                        SafeRange::<$src, $dst, DefaultApprox>::MIN,
+                        SafeRange::<$src, $dst, DefaultApprox>::MAX,
+                        SafeRange::<$src, $dst, RoundToZero>::MIN,
+                        SafeRange::<$src, $dst, RoundToZero>::MAX,
+                        SafeRange::<$src, $dst, RoundToNearest>::MIN,
+                        SafeRange::<$src, $dst, RoundToNearest>::MAX,
+                        SafeRange::<$src, $dst, RoundToNegInf>::MIN,
+                        SafeRange::<$src, $dst, RoundToNegInf>::MAX,
+                        SafeRange::<$src, $dst, RoundToPosInf>::MIN,
+                        SafeRange::<$src, $dst, RoundToPosInf>::MAX,
+                    ]
+                }
+            }
+        )*
+    };
+}
+
+float_to_int_boundaries! { f32 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize }
+float_to_int_boundaries! { f64 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize }
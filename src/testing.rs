@@ -0,0 +1,198 @@
+//! This module provides property checks for conversion implementations, for
+//! use in tests.
+//!
+//! Each function runs a `quickcheck` property over randomly generated
+//! values, and panics with a counterexample if the property does not hold.
+//! They are meant to be called from a crate's own tests, to check that its
+//! `ValueFrom` and `ApproxFrom` implementations follow the same rules as the
+//! builtin ones:
+//!
+//! - [`check_value_roundtrip`] checks that value conversions round-trip
+//!   exactly.
+//! - [`check_approx_bounds`] checks that an approximate conversion only
+//!   overflows at the ends of its range, and preserves ordering.
+//! - [`check_error_kind_consistency`] checks that errors carry the input
+//!   value, and that `ValueFrom` and `ApproxFrom` agree.
+//!
+//! The error types involved must convert into [`GeneralError<Src>`], as all
+//! of this crate's error types do.
+//!
+//! This module requires the `quickcheck` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use conv2::testing::*;
+//! use conv2::{DefaultApprox, RoundToNearest};
+//!
+//! check_value_roundtrip::<i32, u16>();
+//! check_approx_bounds::<f64, i8, RoundToNearest>();
+//! check_error_kind_consistency::<i64, u32>();
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt::Debug;
+
+use quickcheck::{Arbitrary, QuickCheck, TestResult};
+
+use crate::errors::{ConvError, GeneralError};
+use crate::{ApproxFrom, ApproxScheme, DefaultApprox, ValueFrom};
+
+/// Returns `true` if `a` and `b` are equal, treating NaN as equal to itself.
+#[allow(clippy::eq_op)]
+fn same<T: PartialEq>(a: &T, b: &T) -> bool {
+    a == b || (a != a && b != b)
+}
+
+/// Checks that `ValueFrom` conversions between `A` and `B` round-trip.
+///
+/// Whenever a value converts successfully in either direction, converting
+/// the result back must succeed and produce the original value.
+///
+/// # Panics
+///
+/// Panics if a counterexample is found.
+pub fn check_value_roundtrip<A, B>()
+where
+    A: Arbitrary + Debug + PartialEq + ValueFrom<B>,
+    B: Arbitrary + Debug + PartialEq + ValueFrom<A>,
+{
+    QuickCheck::new().quickcheck(roundtrip::<A, B> as fn(A) -> TestResult);
+    QuickCheck::new().quickcheck(roundtrip::<B, A> as fn(B) -> TestResult);
+}
+
+fn roundtrip<A, B>(a: A) -> TestResult
+where
+    A: Clone + Debug + PartialEq + ValueFrom<B>,
+    B: Debug + ValueFrom<A>,
+{
+    let b = match B::value_from(a.clone()) {
+        Ok(b) => b,
+        Err(_) => return TestResult::discard(),
+    };
+    let repr = format!("{:?}", b);
+    match A::value_from(b) {
+        Ok(back) if same(&back, &a) => TestResult::passed(),
+        Ok(back) => TestResult::error(format!(
+            "{:?} converted to {} but back to {:?}",
+            a, repr, back
+        )),
+        Err(_) => TestResult::error(format!(
+            "{:?} converted to {} but could not be converted back",
+            a, repr
+        )),
+    }
+}
+
+/// Checks that `ApproxFrom<Src, Scheme>` conversions into `Dst` only fail at
+/// the ends of the source range, and preserve ordering.
+///
+/// For any two values `a <= b`:
+///
+/// - If `b` fails with a negative overflow, so must `a`.
+/// - If `a` fails with a positive overflow, so must `b`.
+/// - If both succeed, the result for `a` must not be greater than the result
+///   for `b`.
+///
+/// This does not hold for the `Wrapping` scheme, nor for `f64` → `f32`,
+/// which fails on large finite values but passes infinities through.
+///
+/// # Panics
+///
+/// Panics if a counterexample is found.
+pub fn check_approx_bounds<Src, Dst, Scheme>()
+where
+    Src: Arbitrary + Debug + PartialOrd,
+    Dst: ApproxFrom<Src, Scheme> + Debug + PartialOrd + 'static,
+    Dst::Err: Into<GeneralError<Src>>,
+    Scheme: ApproxScheme + 'static,
+{
+    QuickCheck::new().quickcheck(approx_bounds::<Src, Dst, Scheme> as fn(Src, Src) -> TestResult);
+}
+
+fn approx_bounds<Src, Dst, Scheme>(a: Src, b: Src) -> TestResult
+where
+    Src: Clone + Debug + PartialOrd,
+    Dst: ApproxFrom<Src, Scheme> + Debug + PartialOrd,
+    Dst::Err: Into<GeneralError<Src>>,
+    Scheme: ApproxScheme,
+{
+    let (a, b) = match a.partial_cmp(&b) {
+        Some(Ordering::Greater) => (b, a),
+        Some(_) => (a, b),
+        None => return TestResult::discard(),
+    };
+    let ra = Dst::approx_from(a.clone()).map_err(Into::into);
+    let rb = Dst::approx_from(b.clone()).map_err(Into::into);
+    let ok = match (&ra, &rb) {
+        (Ok(da), Ok(db)) => da <= db,
+        (Err(GeneralError::PosOverflow(_)), rb) => matches!(rb, Err(GeneralError::PosOverflow(_))),
+        (ra, Err(GeneralError::NegOverflow(_))) => matches!(ra, Err(GeneralError::NegOverflow(_))),
+        _ => true,
+    };
+    if ok {
+        TestResult::passed()
+    } else {
+        TestResult::error(format!(
+            "{:?} <= {:?}, but they converted to {:?} and {:?}",
+            a, b, ra, rb
+        ))
+    }
+}
+
+/// Checks that errors from `ValueFrom<Src>` and `ApproxFrom<Src>` (with the
+/// `DefaultApprox` scheme) for `Dst` are consistent with each other.
+///
+/// - Every error must carry the input value.
+/// - If the value conversion succeeds, the approximate conversion must
+///   produce the same value.
+/// - If the approximate conversion fails, the value conversion must fail with
+///   the same kind of error.
+///
+/// # Panics
+///
+/// Panics if a counterexample is found.
+pub fn check_error_kind_consistency<Src, Dst>()
+where
+    Src: Arbitrary + Debug + PartialEq,
+    Dst: ValueFrom<Src> + ApproxFrom<Src> + Debug + PartialEq + 'static,
+    <Dst as ValueFrom<Src>>::Err: Into<GeneralError<Src>>,
+    <Dst as ApproxFrom<Src>>::Err: Into<GeneralError<Src>>,
+{
+    QuickCheck::new().quickcheck(error_kinds::<Src, Dst> as fn(Src) -> TestResult);
+}
+
+fn error_kinds<Src, Dst>(src: Src) -> TestResult
+where
+    Src: Clone + Debug + PartialEq,
+    Dst: ValueFrom<Src> + ApproxFrom<Src> + Debug + PartialEq,
+    <Dst as ValueFrom<Src>>::Err: Into<GeneralError<Src>>,
+    <Dst as ApproxFrom<Src>>::Err: Into<GeneralError<Src>>,
+{
+    let value = <Dst as ValueFrom<Src>>::value_from(src.clone()).map_err(Into::into);
+    let approx =
+        <Dst as ApproxFrom<Src, DefaultApprox>>::approx_from(src.clone()).map_err(Into::into);
+
+    for err in [&value, &approx]
+        .into_iter()
+        .filter_map(|r| r.as_ref().err())
+    {
+        if !same(err.as_inner(), &src) {
+            return TestResult::error(format!("converting {:?} produced {:?}", src, err));
+        }
+    }
+    let ok = match (&value, &approx) {
+        (Ok(v), Ok(a)) => same(v, a),
+        (Ok(_), Err(_)) => false,
+        (Err(_), Ok(_)) => true,
+        (Err(v), Err(a)) => v.kind() == a.kind(),
+    };
+    if ok {
+        TestResult::passed()
+    } else {
+        TestResult::error(format!(
+            "converting {:?} produced {:?} as a value, but {:?} as an approximation",
+            src, value, approx
+        ))
+    }
+}
@@ -0,0 +1,110 @@
+use conv2::arith::ArithError;
+use conv2::arith::ArithError::{NegOverflow, PosOverflow, Unrepresentable};
+use conv2::prelude::*;
+use conv2::{ConvError, MapPayload};
+
+#[test]
+fn test_same_type() {
+    assert_eq!(i8::MAX.add_value(0i8), Ok(127));
+    assert_eq!(i8::MAX.add_value(1i8), Err(PosOverflow((127, 1))));
+    assert_eq!(i8::MIN.add_value(-1i8), Err(NegOverflow((-128, -1))));
+    assert_eq!(i8::MIN.sub_value(1i8), Err(NegOverflow((-128, 1))));
+    assert_eq!(0i8.sub_value(i8::MIN), Err(PosOverflow((0, -128))));
+    assert_eq!(u8::MAX.add_value(1u8), Err(PosOverflow((255, 1))));
+    assert_eq!(0usize.sub_value(1usize), Err(NegOverflow((0, 1))));
+
+    assert_eq!(i16::MAX.mul_value(-1i16), Ok(-i16::MAX));
+    assert_eq!(i16::MIN.mul_value(-1i16), Err(PosOverflow((i16::MIN, -1))));
+    assert_eq!(300i16.mul_value(-300i16), Err(NegOverflow((300, -300))));
+    assert_eq!((-300i16).mul_value(-300i16), Err(PosOverflow((-300, -300))));
+    assert_eq!(u32::MAX.mul_value(2u32), Err(PosOverflow((u32::MAX, 2))));
+
+    assert_eq!((-7i64).div_value(2i64), Ok(-3));
+    assert_eq!((-7i64).rem_value(2i64), Ok(-1));
+    assert_eq!(i64::MIN.div_value(-1i64), Err(PosOverflow((i64::MIN, -1))));
+    assert_eq!(i64::MIN.rem_value(-1i64), Ok(0));
+    assert_eq!(1isize.div_value(0isize), Err(Unrepresentable((1, 0))));
+    assert_eq!(1isize.rem_value(0isize), Err(Unrepresentable((1, 0))));
+    assert_eq!(1u64.div_value(0u64), Err(Unrepresentable((1, 0))));
+}
+
+#[test]
+fn test_mixed_types() {
+    assert_eq!(40u32.add_value(2u8), Ok(42));
+    assert_eq!(40i32.sub_value(-2i64), Ok(42));
+    // The operand must fit in the destination type, even if the result would.
+    assert_eq!(40u32.sub_value(-2i64), Err(NegOverflow((40, -2))));
+    assert_eq!(40u32.add_value(-1i64), Err(NegOverflow((40, -1))));
+    assert_eq!(40u32.add_value(u64::MAX), Err(PosOverflow((40, u64::MAX))));
+    assert_eq!(100i8.add_value(100u64), Err(PosOverflow((100, 100))));
+    assert_eq!((-100i8).mul_value(200u8), Err(PosOverflow((-100, 200))));
+    assert_eq!((-100i8).div_value(-200i32), Err(NegOverflow((-100, -200))));
+}
+
+#[test]
+fn test_error_model() {
+    // Conversion and arithmetic failures can be handled together.
+    fn total(values: &[i64]) -> Result<u16, GeneralErrorKind> {
+        values
+            .iter()
+            .try_fold(0u16, |acc, &v| acc.add_value(v))
+            .map_err(GeneralErrorKind::from)
+    }
+
+    assert_eq!(total(&[1, 2, 3]), Ok(6));
+    assert_eq!(total(&[1, -2, 3]), Err(GeneralErrorKind::NegOverflow));
+    assert_eq!(total(&[60_000, 6_000]), Err(GeneralErrorKind::PosOverflow));
+
+    let e = 250u8.add_value(10u8).unwrap_err();
+    assert_eq!(e.kind(), GeneralErrorKind::PosOverflow);
+    assert_eq!(e.into_inner(), (250, 10));
+}
+
+#[test]
+fn test_error() {
+    fn checked(a: u8, b: i64) -> Result<u8, Box<dyn std::error::Error>> {
+        Ok(a.add_value(b)?)
+    }
+
+    let e = checked(250, 10).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "arithmetic resulted in positive overflow: (250, 10)"
+    );
+    assert_eq!(
+        format!("{:#}", 1u8.div_value(0u8).unwrap_err()),
+        "Unrepresentable: arithmetic result is not representable: (1, 0)"
+    );
+    assert_eq!(
+        GeneralError::from(ArithError::NegOverflow((0u8, 1u8))),
+        GeneralError::NegOverflow((0, 1))
+    );
+    assert_eq!(
+        ArithError::new(GeneralErrorKind::PosOverflow, 1i8, 2u64),
+        PosOverflow((1, 2))
+    );
+}
+
+#[test]
+fn test_conv_error() {
+    fn describe<E>(e: E) -> (GeneralErrorKind, String)
+    where
+        E: ConvError,
+        E::Payload: std::fmt::Debug,
+    {
+        (e.kind(), format!("{:?}", e.as_inner()))
+    }
+
+    let e = 5u32.sub_value(6u8).unwrap_err();
+    assert_eq!(
+        describe(e),
+        (GeneralErrorKind::NegOverflow, "(5, 6)".to_string())
+    );
+    assert_eq!(ConvError::into_inner(e), (5, 6));
+
+    let e = 7i16.rem_value(0i16).unwrap_err();
+    assert_eq!(
+        e.map_payload(|(l, r)| (l.to_string(), r.to_string())),
+        Unrepresentable(("7".to_string(), "0".to_string()))
+    );
+}
@@ -0,0 +1,177 @@
+use conv2::batch::{approx_slice, saturate_slice, BatchApproxFrom};
+use conv2::misc::Saturated;
+use conv2::*;
+
+/// Checks the slice functions against converting one element at a time.
+fn check<Src, Dst, Scheme>(src: &[Src]) -> bool
+where
+    Src: Copy + PartialEq + std::fmt::Debug,
+    Dst: BatchApproxFrom<Src, Scheme> + Saturated + Copy + Default + PartialEq,
+    Scheme: ApproxScheme,
+{
+    let mut expected = vec![Dst::default(); src.len()];
+    let mut expected_err = None;
+    for (i, &s) in src.iter().enumerate() {
+        match <Dst as ApproxFrom<Src, Scheme>>::approx_from(s) {
+            Ok(d) => expected[i] = d,
+            Err(e) => {
+                expected_err = Some((i, e));
+                break;
+            }
+        }
+    }
+    let mut dst = vec![Dst::default(); src.len()];
+    let result = approx_slice::<Src, Dst, Scheme>(src, &mut dst);
+    // NaN payloads never compare equal, so the errors are compared by their
+    // debug output.
+    let same =
+        |a: &dyn std::fmt::Debug, b: &dyn std::fmt::Debug| format!("{a:?}") == format!("{b:?}");
+    if !same(&result, &expected_err.map_or(Ok(()), Err)) || dst != expected {
+        return false;
+    }
+
+    let mut expected = vec![Dst::default(); src.len()];
+    let mut expected_err = None;
+    for (i, &s) in src.iter().enumerate() {
+        match <Dst as ApproxFrom<Src, Scheme>>::approx_from(s).saturate() {
+            Ok(d) => expected[i] = d,
+            Err(e) => {
+                expected_err = Some((i, e));
+                break;
+            }
+        }
+    }
+    let mut dst = vec![Dst::default(); src.len()];
+    let result = saturate_slice::<Src, Dst, Scheme>(src, &mut dst);
+    same(&result, &expected_err.map_or(Ok(()), Err)) && dst == expected
+}
+
+fn check_all_schemes<Src, Dst>(src: &[Src]) -> bool
+where
+    Src: Copy + PartialEq + std::fmt::Debug,
+    Dst: BatchApproxFrom<Src, DefaultApprox>
+        + BatchApproxFrom<Src, RoundToZero>
+        + BatchApproxFrom<Src, RoundToNearest>
+        + BatchApproxFrom<Src, RoundToNegInf>
+        + BatchApproxFrom<Src, RoundToPosInf>
+        + Saturated
+        + Copy
+        + Default
+        + PartialEq,
+{
+    check::<Src, Dst, DefaultApprox>(src)
+        && check::<Src, Dst, RoundToZero>(src)
+        && check::<Src, Dst, RoundToNearest>(src)
+        && check::<Src, Dst, RoundToNegInf>(src)
+        && check::<Src, Dst, RoundToPosInf>(src)
+}
+
+#[test]
+fn test_approx_slice() {
+    let src: Vec<f32> = (0..200).map(|i| i as f32 * 0.75 - 50.0).collect();
+    let mut dst = vec![0i32; 200];
+    assert_eq!(approx_slice::<_, _, RoundToNearest>(&src, &mut dst), Ok(()));
+    assert_eq!(dst[0], -50);
+    assert_eq!(dst[199], 99);
+
+    // A failure in a later chunk keeps the earlier results.
+    let mut src = vec![1.0f32; 200];
+    src[150] = f32::NAN;
+    src[160] = 1e10;
+    let mut dst = vec![0u8; 200];
+    assert!(matches!(
+        approx_slice::<_, _, DefaultApprox>(&src, &mut dst),
+        Err((150, FloatError::NotANumber(_)))
+    ));
+    assert!(dst[..150].iter().all(|&d| d == 1));
+    assert!(dst[150..].iter().all(|&d| d == 0));
+
+    let src = [0.5f64, -0.5];
+    let mut dst = [0u64; 2];
+    assert_eq!(
+        approx_slice::<_, _, RoundToNegInf>(&src, &mut dst),
+        Err((1, FloatError::NegOverflow(-0.5)))
+    );
+    assert_eq!(dst, [0, 0]);
+
+    assert_eq!(
+        approx_slice::<f32, i32, DefaultApprox>(&[], &mut []),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_saturate_slice() {
+    let src = [f32::NEG_INFINITY, -1e20, -0.5, 3.5, 1e20, f32::INFINITY];
+    let mut dst = [0i16; 6];
+    assert_eq!(
+        saturate_slice::<_, _, RoundToNearest>(&src, &mut dst),
+        Ok(())
+    );
+    assert_eq!(dst, [i16::MIN, i16::MIN, -1, 4, i16::MAX, i16::MAX]);
+
+    let mut src = vec![-3.0f64; 100];
+    src[70] = f64::NAN;
+    let mut dst = vec![7u32; 100];
+    assert!(matches!(
+        saturate_slice::<_, _, DefaultApprox>(&src, &mut dst),
+        Err((70, Unrepresentable(_)))
+    ));
+    assert!(dst[..70].iter().all(|&d| d == 0));
+    assert!(dst[70..].iter().all(|&d| d == 7));
+}
+
+#[test]
+#[should_panic]
+fn test_length_mismatch() {
+    let _ = approx_slice::<f32, i32, DefaultApprox>(&[1.0, 2.0], &mut [0]);
+}
+
+/// Values near the bounds of every destination type, and a few specials.
+fn edges() -> Vec<f64> {
+    let mut edges = vec![0.0, -0.0, 0.5, -0.5, 1.5, -1.5, f64::NAN, f64::INFINITY];
+    for e in [7, 8, 15, 16, 31, 32, 63, 64] {
+        let b = 2f64.powi(e);
+        edges.extend_from_slice(&[b, -b, b - 0.5, -b - 0.5, b - 1.0, -b + 1.0]);
+    }
+    edges
+}
+
+#[test]
+fn test_matches_approx_from() {
+    fn property(values: Vec<u8>, seed: Vec<f64>) -> bool {
+        if seed.is_empty() {
+            return true;
+        }
+        // Mixes random values with edges, over lengths spanning several chunks.
+        let edges = edges();
+        let src: Vec<f64> = values
+            .iter()
+            .zip(seed.iter().cycle())
+            .map(|(&v, &s)| {
+                if v % 4 == 0 {
+                    s
+                } else {
+                    edges[v as usize % edges.len()]
+                }
+            })
+            .collect();
+        let src32: Vec<f32> = src.iter().map(|&s| s as f32).collect();
+        check_all_schemes::<f64, i8>(&src)
+            && check_all_schemes::<f64, i32>(&src)
+            && check_all_schemes::<f64, u16>(&src)
+            && check_all_schemes::<f64, u64>(&src)
+            && check_all_schemes::<f64, isize>(&src)
+            && check_all_schemes::<f32, u8>(&src32)
+            && check_all_schemes::<f32, i16>(&src32)
+            && check_all_schemes::<f32, i64>(&src32)
+            && check_all_schemes::<f32, u32>(&src32)
+            && check_all_schemes::<f32, usize>(&src32)
+    }
+
+    for n in [0usize, 1, 63, 64, 65, 300] {
+        let values: Vec<u8> = (0..n).map(|i| (i * 37 % 256) as u8).collect();
+        assert!(property(values, vec![1.0, -2.5, 1e30]));
+    }
+    quickcheck::quickcheck(property as fn(Vec<u8>, Vec<f64>) -> bool);
+}
@@ -0,0 +1,197 @@
+#![cfg(feature = "num-bigint")]
+
+use conv2::*;
+use num_bigint::{BigInt, BigUint};
+
+fn big(v: i128) -> BigInt {
+    BigInt::from(v)
+}
+
+fn pow2(e: u64) -> BigInt {
+    BigInt::from(1) << e
+}
+
+#[test]
+fn test_value_from_bigint() {
+    assert_eq!(i8::value_from(big(-128)), Ok(-128));
+    assert_eq!(
+        i8::value_from(big(-129)),
+        Err(RangeError::NegOverflow(big(-129)))
+    );
+    assert_eq!(
+        i8::value_from(big(128)),
+        Err(RangeError::PosOverflow(big(128)))
+    );
+    assert_eq!(
+        u8::value_from(big(-1)),
+        Err(RangeError::NegOverflow(big(-1)))
+    );
+    assert_eq!(u64::value_from(pow2(64) - 1), Ok(u64::MAX));
+    assert_eq!(
+        u64::value_from(pow2(64)),
+        Err(RangeError::PosOverflow(pow2(64)))
+    );
+    assert_eq!(i64::value_from(-pow2(63)), Ok(i64::MIN));
+    assert_eq!(usize::value_from(big(7)), Ok(7));
+
+    let max = BigUint::from(u32::MAX);
+    assert_eq!(u32::value_from(max.clone()), Ok(u32::MAX));
+    assert_eq!(i32::value_from(max.clone()), Err(PosOverflow(max)));
+}
+
+#[test]
+fn test_value_into_bigint() {
+    assert_eq!(BigInt::value_from(i64::MIN), Ok(-pow2(63)));
+    assert_eq!(BigInt::value_from(u64::MAX), Ok(pow2(64) - 1));
+    assert_eq!(BigUint::value_from(u8::MAX), Ok(BigUint::from(255u8)));
+    assert_eq!(BigUint::value_from(-1isize), Err(NegOverflow(-1)));
+
+    assert_eq!(BigInt::value_from(BigUint::from(5u8)), Ok(big(5)));
+    assert_eq!(BigUint::value_from(big(5)), Ok(BigUint::from(5u8)));
+    assert_eq!(BigUint::value_from(big(-5)), Err(NegOverflow(big(-5))));
+}
+
+#[test]
+fn test_bigint_to_float() {
+    macro_rules! approx {
+        ($dst:ty, $scheme:ty, $src:expr) => {
+            <$dst as ApproxFrom<BigInt, $scheme>>::approx_from($src)
+        };
+    }
+
+    // Exactly representable values are unaffected by the scheme.
+    assert_eq!(approx!(f32, RoundToZero, pow2(24)), Ok(16_777_216.0));
+    assert_eq!(
+        approx!(f64, RoundToPosInf, -pow2(53)),
+        Ok(-9_007_199_254_740_992.0)
+    );
+
+    // 2^24 + 1 lies halfway between two `f32`s.
+    let tie: BigInt = pow2(24) + 1;
+    assert_eq!(approx!(f32, DefaultApprox, tie.clone()), Ok(16_777_216.0));
+    assert_eq!(approx!(f32, RoundToNearest, tie.clone()), Ok(16_777_218.0));
+    assert_eq!(approx!(f32, RoundToZero, tie.clone()), Ok(16_777_216.0));
+    assert_eq!(approx!(f32, RoundToNegInf, tie.clone()), Ok(16_777_216.0));
+    assert_eq!(approx!(f32, RoundToPosInf, tie.clone()), Ok(16_777_218.0));
+    assert_eq!(
+        approx!(f32, RoundToNearest, -tie.clone()),
+        Ok(-16_777_218.0)
+    );
+    assert_eq!(approx!(f32, RoundToNegInf, -tie.clone()), Ok(-16_777_218.0));
+    assert_eq!(approx!(f32, RoundToPosInf, -tie), Ok(-16_777_216.0));
+
+    // Ties to even round up when the truncated mantissa is odd.
+    assert_eq!(approx!(f32, DefaultApprox, pow2(24) + 3), Ok(16_777_220.0));
+    // Anything past the halfway point rounds up to nearest.
+    assert_eq!(
+        approx!(f64, DefaultApprox, pow2(80) + pow2(27) + 1),
+        Ok(2f64.powi(80) + 2f64.powi(28))
+    );
+
+    // DefaultApprox agrees with `as` for values that fit in a `u64`.
+    for v in [
+        u64::MAX,
+        u64::MAX - 1,
+        (1 << 63) + (1 << 39),
+        0x0123_4567_89ab_cdef,
+    ] {
+        assert_eq!(approx!(f32, DefaultApprox, BigInt::from(v)), Ok(v as f32));
+        assert_eq!(approx!(f64, DefaultApprox, BigInt::from(v)), Ok(v as f64));
+    }
+
+    // The largest finite values, and rounding past them.
+    let f32_max = BigInt::from(f32::MAX as u128);
+    assert_eq!(approx!(f32, RoundToNearest, f32_max.clone()), Ok(f32::MAX));
+    assert_eq!(approx!(f32, RoundToZero, f32_max.clone() + 1), Ok(f32::MAX));
+    assert_eq!(
+        approx!(f32, RoundToPosInf, f32_max.clone() + 1),
+        Err(RangeError::PosOverflow(f32_max.clone() + 1))
+    );
+    assert_eq!(
+        approx!(f32, RoundToNegInf, -f32_max.clone() - 1),
+        Err(RangeError::NegOverflow(-f32_max - 1))
+    );
+    assert_eq!(approx!(f64, RoundToZero, pow2(1024) - 1), Ok(f64::MAX));
+    assert_eq!(
+        approx!(f64, RoundToNearest, pow2(1024) - 1),
+        Err(RangeError::PosOverflow(pow2(1024) - 1))
+    );
+
+    assert_eq!(
+        <f64 as ApproxFrom<BigUint, RoundToPosInf>>::approx_from(BigUint::from(u64::MAX)),
+        Ok(18_446_744_073_709_551_616.0)
+    );
+}
+
+#[test]
+fn test_float_to_bigint() {
+    macro_rules! approx {
+        ($dst:ty, $scheme:ty, $src:expr) => {
+            <$dst as ApproxFrom<_, $scheme>>::approx_from($src)
+        };
+    }
+
+    assert_eq!(approx!(BigInt, DefaultApprox, -2.5f64), Ok(big(-2)));
+    assert_eq!(approx!(BigInt, RoundToNearest, -2.5f64), Ok(big(-3)));
+    assert_eq!(approx!(BigInt, RoundToZero, -2.5f64), Ok(big(-2)));
+    assert_eq!(approx!(BigInt, RoundToNegInf, -2.5f64), Ok(big(-3)));
+    assert_eq!(approx!(BigInt, RoundToPosInf, -2.5f32), Ok(big(-2)));
+    assert_eq!(approx!(BigInt, RoundToPosInf, 1e-300f64), Ok(big(1)));
+    assert_eq!(approx!(BigInt, RoundToZero, 0.0f64), Ok(big(0)));
+
+    // Large values are converted exactly.
+    assert_eq!(
+        approx!(BigInt, DefaultApprox, 2f64.powi(100)),
+        Ok(pow2(100))
+    );
+    assert_eq!(
+        approx!(BigInt, DefaultApprox, f32::MIN),
+        Ok(-BigInt::from(f32::MAX as u128))
+    );
+    assert_eq!(
+        approx!(BigInt, DefaultApprox, f64::MAX),
+        Ok((pow2(53) - 1) << 971u64)
+    );
+
+    assert!(matches!(
+        approx!(BigInt, DefaultApprox, f64::NAN),
+        Err(FloatError::NotANumber(_))
+    ));
+    assert_eq!(
+        approx!(BigInt, DefaultApprox, f64::INFINITY),
+        Err(FloatError::PosOverflow(f64::INFINITY))
+    );
+    assert_eq!(
+        approx!(BigInt, DefaultApprox, f32::NEG_INFINITY),
+        Err(FloatError::NegOverflow(f32::NEG_INFINITY))
+    );
+
+    assert_eq!(
+        approx!(BigUint, RoundToZero, -0.5f64),
+        Ok(BigUint::from(0u8))
+    );
+    assert_eq!(
+        approx!(BigUint, RoundToNegInf, -0.5f64),
+        Err(FloatError::NegOverflow(-0.5))
+    );
+}
+
+#[test]
+fn test_float_roundtrip() {
+    // Every finite float is an exact BigInt after rounding, so converting back
+    // with any scheme is lossless.
+    let values = [
+        1.0f64,
+        -1.0,
+        3.0e15,
+        -7.5e20,
+        2f64.powi(-3),
+        123_456_789.0,
+        f64::MIN,
+    ];
+    for v in values {
+        let b: BigInt = ApproxFrom::<_, RoundToZero>::approx_from(v).unwrap();
+        let back: f64 = ApproxFrom::<_, RoundToPosInf>::approx_from(b).unwrap();
+        assert_eq!(back, v.trunc());
+    }
+}
@@ -0,0 +1,124 @@
+use conv2::bounded::Bounded;
+use conv2::*;
+
+type Percent = Bounded<u8, 0, 100>;
+type Port = Bounded<u16, 1, 65535>;
+type Offset = Bounded<i32, -10, 10>;
+
+#[test]
+fn test_new() {
+    assert_eq!(Percent::new(0).map(Percent::get), Some(0));
+    assert_eq!(Percent::new(100).map(Percent::get), Some(100));
+    assert_eq!(Percent::new(101), None);
+    assert_eq!(Port::new(0), None);
+    assert_eq!(Offset::new(-11), None);
+    assert_eq!(Offset::new(-10).map(i32::from), Some(-10));
+    assert_eq!(Offset::new(-3).unwrap().to_string(), "-3");
+    assert_eq!(format!("{:>4}", Percent::new(42).unwrap()), "  42");
+
+    assert_eq!(Percent::MIN, 0);
+    assert_eq!(Percent::MAX, 100);
+    assert_eq!(Offset::MIN, -10);
+    assert_eq!(Bounded::<i64, { i64::MIN as i128 }, 0>::MIN, i64::MIN);
+    assert_eq!(Bounded::<u64, 0, { u64::MAX as i128 }>::MAX, u64::MAX);
+
+    const P: Option<Percent> = Percent::new(50);
+    assert!(P.is_some());
+}
+
+#[test]
+fn test_value_from() {
+    assert_eq!(Percent::value_from(42u8).map(Percent::get), Ok(42));
+    assert_eq!(Percent::value_from(100i64).map(Percent::get), Ok(100));
+    assert_eq!(
+        Percent::value_from(101u8),
+        Err(RangeError::PosOverflow(101))
+    );
+    assert_eq!(Percent::value_from(-1i8), Err(RangeError::NegOverflow(-1)));
+
+    // Failures of the underlying conversion are reported the same way.
+    assert_eq!(
+        Percent::value_from(1000i32),
+        Err(RangeError::PosOverflow(1000))
+    );
+    assert_eq!(
+        Percent::value_from(-1000i32),
+        Err(RangeError::NegOverflow(-1000))
+    );
+
+    assert_eq!(Port::value_from(0usize), Err(RangeError::NegOverflow(0)));
+    assert_eq!(Port::value_from(443u64).map(Port::get), Ok(443));
+    assert_eq!(
+        Port::value_from(65536u32),
+        Err(RangeError::PosOverflow(65536))
+    );
+
+    assert_eq!(Offset::value_from(-10i8).map(Offset::get), Ok(-10));
+    assert_eq!(Offset::value_from(11u64), Err(RangeError::PosOverflow(11)));
+
+    let x: Result<Percent, _> = 7isize.value_into();
+    assert_eq!(x.map(Percent::get), Ok(7));
+}
+
+#[test]
+fn test_approx_from() {
+    assert_eq!(
+        <Percent as ApproxFrom<_>>::approx_from(99.9f64).map(Percent::get),
+        Ok(99)
+    );
+    assert_eq!(
+        <Percent as ApproxFrom<_, RoundToNearest>>::approx_from(99.9f32).map(Percent::get),
+        Ok(100)
+    );
+    assert_eq!(
+        <Percent as ApproxFrom<_, RoundToNearest>>::approx_from(100.5f32),
+        Err(FloatError::PosOverflow(100.5))
+    );
+    assert_eq!(
+        <Percent as ApproxFrom<_>>::approx_from(300.0f64),
+        Err(FloatError::PosOverflow(300.0))
+    );
+    assert_eq!(
+        <Offset as ApproxFrom<_>>::approx_from(-10.9f64).map(Offset::get),
+        Ok(-10)
+    );
+    assert_eq!(
+        <Offset as ApproxFrom<_, RoundToNegInf>>::approx_from(-10.1f64),
+        Err(FloatError::NegOverflow(-10.1))
+    );
+    assert!(matches!(
+        <Percent as ApproxFrom<_>>::approx_from(f32::NAN),
+        Err(FloatError::NotANumber(_))
+    ));
+
+    // Wrapping applies to the underlying type, before the bounds are checked.
+    assert_eq!(
+        <Percent as ApproxFrom<_, Wrapping>>::approx_from(260i32).map(Percent::get),
+        Ok(4)
+    );
+    assert_eq!(
+        <Percent as ApproxFrom<_, Wrapping>>::approx_from(200i32),
+        Err(RangeError::PosOverflow(200))
+    );
+
+    let p: Result<Percent, _> = 50u64.approx();
+    assert_eq!(p.map(Percent::get), Ok(50));
+}
+
+#[test]
+fn test_saturate() {
+    assert_eq!(Percent::value_from(250u8).unwrap_or_saturate().get(), 100);
+    assert_eq!(Percent::value_from(-5i32).unwrap_or_saturate().get(), 0);
+    assert_eq!(Port::value_from(0u16).unwrap_or_saturate().get(), 1);
+    assert_eq!(Offset::value_from(i64::MIN).unwrap_or_saturate().get(), -10);
+
+    assert_eq!(
+        <Percent as ApproxFrom<_>>::approx_from(1e10f64)
+            .saturate()
+            .map(Percent::get),
+        Ok(100)
+    );
+    assert!(<Percent as ApproxFrom<_>>::approx_from(f64::NAN)
+        .saturate()
+        .is_err());
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "num-complex")]
+
+use conv2::complex::ComplexError;
+use conv2::*;
+use num_complex::Complex;
+
+#[test]
+fn test_value_from_complex() {
+    assert_eq!(
+        Complex::<i32>::value_from(Complex::new(-3i16, 4)),
+        Ok(Complex::new(-3, 4))
+    );
+    assert_eq!(
+        Complex::<f64>::value_from(Complex::new(i16::MIN, i16::MAX)),
+        Ok(Complex::new(-32_768.0, 32_767.0))
+    );
+    assert_eq!(
+        Complex::<f64>::value_from(Complex::new(0.5f32, 1.5)),
+        Ok(Complex::new(0.5, 1.5))
+    );
+    assert_eq!(
+        Complex::<u8>::value_from(Complex::new(-1i32, 300)),
+        Err(ComplexError::Re(RangeError::NegOverflow(-1)))
+    );
+    assert_eq!(
+        Complex::<u8>::value_from(Complex::new(1i32, 300)),
+        Err(ComplexError::Im(RangeError::PosOverflow(300)))
+    );
+    assert_eq!(
+        Complex::<f32>::value_from(Complex::new(0i64, (1 << 24) + 1)),
+        Err(ComplexError::Im(RangeError::PosOverflow((1 << 24) + 1)))
+    );
+}
+
+#[test]
+fn test_approx_from_complex() {
+    let z = Complex::new(1.5f64, -2.5);
+    assert_eq!(
+        <Complex<f32> as ApproxFrom<_>>::approx_from(z),
+        Ok(Complex::new(1.5, -2.5))
+    );
+    assert_eq!(
+        <Complex<i16> as ApproxFrom<_, RoundToNearest>>::approx_from(z),
+        Ok(Complex::new(2, -3))
+    );
+    assert_eq!(
+        <Complex<i16> as ApproxFrom<_, RoundToZero>>::approx_from(z),
+        Ok(Complex::new(1, -2))
+    );
+    assert_eq!(
+        <Complex<i16> as ApproxFrom<_, RoundToPosInf>>::approx_from(z),
+        Ok(Complex::new(2, -2))
+    );
+    assert_eq!(
+        <Complex<u16> as ApproxFrom<_, RoundToNegInf>>::approx_from(z),
+        Err(ComplexError::Im(FloatError::NegOverflow(-2.5)))
+    );
+    assert!(matches!(
+        <Complex<i16> as ApproxFrom<_>>::approx_from(Complex::new(f32::NAN, 0.0)),
+        Err(ComplexError::Re(FloatError::NotANumber(_)))
+    ));
+    assert_eq!(
+        <Complex<i8> as ApproxFrom<_, Wrapping>>::approx_from(Complex::new(200i32, -129)),
+        Ok(Complex::new(-56, 127))
+    );
+}
+
+#[test]
+fn test_from_real() {
+    assert_eq!(
+        Complex::<f64>::value_from(2.5f64),
+        Ok(Complex::new(2.5, 0.0))
+    );
+    assert_eq!(Complex::<i16>::value_from(7u8), Ok(Complex::new(7, 0)));
+    assert_eq!(
+        Complex::<u8>::value_from(-7i16),
+        Err(RangeError::NegOverflow(-7))
+    );
+    assert_eq!(
+        <Complex<i16> as ApproxFrom<_, RoundToNearest>>::approx_from(-0.5f32),
+        Ok(Complex::new(-1, 0))
+    );
+    assert_eq!(
+        <Complex<f32> as ApproxFrom<_>>::approx_from(1e300f64),
+        Err(RangeError::PosOverflow(1e300))
+    );
+}
+
+#[test]
+fn test_complex_error() {
+    let e = ComplexError::Im(RangeError::PosOverflow(300i32));
+    assert!(e.is_im());
+    assert!(!e.is_re());
+    assert_eq!(e.kind(), GeneralErrorKind::PosOverflow);
+    assert_eq!(*e.as_inner(), 300);
+    assert_eq!(
+        e.to_string(),
+        "imaginary part: conversion resulted in positive overflow: 300"
+    );
+    assert_eq!(
+        format!("{:#}", e),
+        "imaginary part: PosOverflow: conversion resulted in positive overflow: 300"
+    );
+    assert_eq!(
+        e.map_payload(|v| v as i64 * 2),
+        ComplexError::Im(RangeError::PosOverflow(600i64))
+    );
+    assert_eq!(e.into_error(), RangeError::PosOverflow(300));
+}
@@ -0,0 +1,78 @@
+use conv2::misc::ConversionInfo;
+use conv2::*;
+
+macro_rules! check_info {
+    ($($src:ident => $($dst:ident),*;)*) => {
+        $($(
+            let name = concat!(stringify!($src), " -> ", stringify!($dst));
+            let min = <$dst as ConversionInfo<$src>>::SRC_MIN;
+            let max = <$dst as ConversionInfo<$src>>::SRC_MAX;
+            let can_neg = <$dst as ConversionInfo<$src>>::CAN_NEG_OVERFLOW;
+            let can_pos = <$dst as ConversionInfo<$src>>::CAN_POS_OVERFLOW;
+            let lossless = <$dst as ConversionInfo<$src>>::LOSSLESS;
+
+            assert!(<$dst as ValueFrom<$src>>::value_from(min).is_ok(), "{}", name);
+            assert!(<$dst as ValueFrom<$src>>::value_from(max).is_ok(), "{}", name);
+            assert_eq!(can_neg, min != $src::MIN, "{}", name);
+            assert_eq!(can_pos, max != $src::MAX, "{}", name);
+            assert_eq!(lossless, !can_neg && !can_pos, "{}", name);
+            if can_neg {
+                assert_eq!(
+                    <$dst as ValueFrom<$src>>::value_from(min.wrapping_sub(1)).map_err(GeneralErrorKind::from),
+                    Err(GeneralErrorKind::NegOverflow),
+                    "{}", name
+                );
+            }
+            if can_pos {
+                assert_eq!(
+                    <$dst as ValueFrom<$src>>::value_from(max.wrapping_add(1)).map_err(GeneralErrorKind::from),
+                    Err(GeneralErrorKind::PosOverflow),
+                    "{}", name
+                );
+            }
+        )*)*
+    };
+}
+
+#[test]
+fn test_matches_value_from() {
+    check_info! {
+        i8 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+        i16 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+        i32 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+        i64 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+        isize => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+        u8 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+        u16 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+        u32 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+        u64 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+        usize => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64;
+    }
+}
+
+// The properties are usable in const contexts.
+const _: () = assert!(<f64 as ConversionInfo<f32>>::LOSSLESS);
+const _: () = assert!(!<u8 as ConversionInfo<u16>>::LOSSLESS);
+
+#[test]
+fn test_floats() {
+    assert_eq!(<f64 as ConversionInfo<f32>>::SRC_MIN, f32::NEG_INFINITY);
+    assert_eq!(<f32 as ConversionInfo<f32>>::SRC_MAX, f32::INFINITY);
+}
+
+#[test]
+fn test_generic() {
+    fn describe<Src, Dst: ConversionInfo<Src>>() -> &'static str {
+        match (Dst::CAN_NEG_OVERFLOW, Dst::CAN_POS_OVERFLOW) {
+            (false, false) => "lossless",
+            (true, false) => "negative overflow only",
+            (false, true) => "positive overflow only",
+            (true, true) => "either overflow",
+        }
+    }
+
+    assert_eq!(describe::<u8, u16>(), "lossless");
+    assert_eq!(describe::<i8, u64>(), "negative overflow only");
+    assert_eq!(describe::<u64, i8>(), "positive overflow only");
+    assert_eq!(describe::<i64, f64>(), "either overflow");
+}
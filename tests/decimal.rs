@@ -0,0 +1,111 @@
+#![cfg(feature = "rust_decimal")]
+
+use conv2::*;
+use rust_decimal::Decimal;
+
+fn dec(num: i64, scale: u32) -> Decimal {
+    Decimal::new(num, scale)
+}
+
+#[test]
+fn test_value_into_decimal() {
+    assert_eq!(Decimal::value_from(i64::MIN), Ok(Decimal::from(i64::MIN)));
+    assert_eq!(Decimal::value_from(u64::MAX), Ok(Decimal::from(u64::MAX)));
+    assert_eq!(Decimal::value_from(0u8), Ok(Decimal::ZERO));
+}
+
+#[test]
+fn test_decimal_to_int() {
+    macro_rules! approx {
+        ($dst:ty, $scheme:ty, $src:expr) => {
+            <$dst as ApproxFrom<Decimal, $scheme>>::approx_from($src)
+        };
+    }
+
+    for (src, nearest, half_even, zero, neg_inf, pos_inf) in [
+        (dec(25, 1), 3, 2, 2, 2, 3),
+        (dec(35, 1), 4, 4, 3, 3, 4),
+        (dec(-25, 1), -3, -2, -2, -3, -2),
+        (dec(26, 1), 3, 3, 2, 2, 3),
+        (dec(-24, 1), -2, -2, -2, -3, -2),
+        (dec(7, 0), 7, 7, 7, 7, 7),
+    ] {
+        assert_eq!(approx!(i32, RoundToNearest, src), Ok(nearest));
+        assert_eq!(approx!(i32, RoundHalfEven, src), Ok(half_even));
+        assert_eq!(approx!(i32, RoundToZero, src), Ok(zero));
+        assert_eq!(approx!(i32, RoundToNegInf, src), Ok(neg_inf));
+        assert_eq!(approx!(i32, RoundToPosInf, src), Ok(pos_inf));
+        assert_eq!(approx!(i32, DefaultApprox, src), Ok(zero));
+    }
+
+    // Overflow depends on the rounded value.
+    assert_eq!(approx!(u8, RoundToZero, dec(2559, 1)), Ok(255));
+    assert_eq!(
+        approx!(u8, RoundToNearest, dec(2555, 1)),
+        Err(RangeError::PosOverflow(dec(2555, 1)))
+    );
+    assert_eq!(approx!(u8, RoundToPosInf, dec(-5, 1)), Ok(0));
+    assert_eq!(
+        approx!(u8, RoundToNegInf, dec(-5, 1)),
+        Err(RangeError::NegOverflow(dec(-5, 1)))
+    );
+    assert_eq!(approx!(i64, RoundToZero, Decimal::MAX).ok(), None);
+    assert_eq!(
+        approx!(u64, RoundHalfEven, Decimal::from(u64::MAX)),
+        Ok(u64::MAX)
+    );
+}
+
+#[test]
+fn test_decimal_to_float() {
+    assert_eq!(f64::value_from(dec(0, 0)), Ok(0.0));
+    assert_eq!(f64::value_from(dec(-15, 1)), Ok(-1.5));
+    assert_eq!(f64::value_from(dec(1_500, 3)), Ok(1.5));
+    assert_eq!(f32::value_from(dec(390_625, 8)), Ok(0.003_906_25));
+    assert_eq!(f64::value_from(dec(1, 1)), Err(Unrepresentable(dec(1, 1))));
+    assert_eq!(
+        f64::value_from(Decimal::from(1u64 << 53)),
+        Ok(9_007_199_254_740_992.0)
+    );
+    assert_eq!(
+        f64::value_from(Decimal::from((1u64 << 53) + 1)),
+        Err(Unrepresentable(Decimal::from((1u64 << 53) + 1)))
+    );
+    assert_eq!(
+        f32::value_from(Decimal::from(16_777_217)),
+        Err(Unrepresentable(Decimal::from(16_777_217)))
+    );
+    assert_eq!(f64::value_from(Decimal::MAX).ok(), None);
+    assert_eq!(
+        f64::value_from(Decimal::from(1u128 << 95)),
+        Ok(2f64.powi(95))
+    );
+}
+
+#[test]
+fn test_float_to_decimal() {
+    assert_eq!(Decimal::approx_from(-2.5f64), Ok(dec(-25, 1)));
+    assert_eq!(Decimal::approx_from(0.25f32), Ok(dec(25, 2)));
+    assert_eq!(
+        Decimal::approx_from(0.1f64).map(|d| d.to_string()),
+        Ok("0.1000000000000000055511151231".to_string())
+    );
+    assert!(matches!(
+        Decimal::approx_from(f64::NAN),
+        Err(FloatError::NotANumber(_))
+    ));
+    assert_eq!(
+        Decimal::approx_from(f64::INFINITY),
+        Err(FloatError::PosOverflow(f64::INFINITY))
+    );
+    assert_eq!(
+        Decimal::approx_from(-1e30f64),
+        Err(FloatError::NegOverflow(-1e30))
+    );
+
+    // Exactly representable values survive a round trip.
+    for v in [0.5f64, -1234.0625, 1e20, 3.0] {
+        let d = Decimal::approx_from(v).unwrap();
+        assert_eq!(f64::value_from(d), Ok(v));
+    }
+}
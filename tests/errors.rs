@@ -0,0 +1,345 @@
+use std::io;
+
+use conv2::errors::ConversionError;
+use conv2::*;
+
+#[test]
+fn test_io_error() {
+    let err: io::Error = GeneralError::PosOverflow(300i32).into();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(
+        err.to_string(),
+        "conversion resulted in positive overflow: 300"
+    );
+
+    let err: io::Error = FloatError::NotANumber(f32::NAN).into();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(
+        err.to_string(),
+        "could not convert unrepresentable value: NaN"
+    );
+
+    let err: io::Error = RangeError::NegOverflow(-1i8).into();
+    assert_eq!(
+        err.to_string(),
+        "conversion resulted in negative overflow: -1"
+    );
+    let err: io::Error = NegOverflow(-2i8).into();
+    assert_eq!(
+        err.to_string(),
+        "conversion resulted in negative overflow: -2"
+    );
+    let err: io::Error = PosOverflow(3u8).into();
+    assert_eq!(
+        err.to_string(),
+        "conversion resulted in positive overflow: 3"
+    );
+    let err: io::Error = Unrepresentable(4u8).into();
+    assert_eq!(
+        err.to_string(),
+        "could not convert unrepresentable value: 4"
+    );
+
+    let err: io::Error = GeneralErrorKind::Unrepresentable.into();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "could not convert unrepresentable value");
+    let err: io::Error = RangeErrorKind::PosOverflow.into();
+    assert_eq!(err.to_string(), "conversion resulted in positive overflow");
+}
+
+#[test]
+fn test_io_error_question_mark() {
+    fn read_len(len: u64) -> io::Result<u16> {
+        Ok(len.value_into()?)
+    }
+
+    assert_eq!(read_len(12).unwrap(), 12);
+    assert_eq!(
+        read_len(70_000).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn test_conversion_error() {
+    let err = ConversionError::new::<u8, _, _>(RangeError::NegOverflow(-1i16));
+    assert_eq!(err.kind(), GeneralErrorKind::NegOverflow);
+    assert_eq!(err.value(), "-1");
+    assert_eq!(err.src_type(), "i16");
+    assert_eq!(err.dst_type(), "u8");
+    assert_eq!(
+        err.to_string(),
+        "conversion resulted in negative overflow: -1 (i16 to u8)"
+    );
+
+    let err = 1e10f64
+        .approx_as::<u32>()
+        .map_err(ConversionError::new::<u32, _, _>)
+        .unwrap_err();
+    assert_eq!(err.kind(), GeneralErrorKind::PosOverflow);
+    assert_eq!(err.value(), "10000000000.0");
+
+    let boxed: Box<dyn std::error::Error + Send + Sync> = Box::new(err.clone());
+    assert_eq!(boxed.to_string(), err.to_string());
+
+    let io_err: io::Error = err.into();
+    assert_eq!(io_err.kind(), io::ErrorKind::InvalidData);
+    assert!(io_err.get_ref().unwrap().is::<ConversionError>());
+    // The destination name comes from the type, and payloads only need
+    // `Debug`.
+    let err = ConversionError::new::<u16, _, _>(Unrepresentable(io::SeekFrom::End(-1)));
+    assert_eq!(err.dst_type(), "u16");
+    assert_eq!(err.value(), "End(-1)");
+    assert_eq!(
+        format!("{:#}", err),
+        "Unrepresentable: could not convert unrepresentable value: End(-1) (std::io::SeekFrom to u16)"
+    );
+}
+
+#[test]
+fn test_with_context() {
+    let err = 300i32.value_as::<u8>().with_context().unwrap_err();
+    assert_eq!(err.kind, GeneralErrorKind::PosOverflow);
+    assert_eq!(err.value, 300);
+    assert_eq!((err.min, err.max), (0u8, 255u8));
+    assert_eq!(err.src_type(), "i32");
+    assert_eq!(err.dst_type(), "u8");
+    assert_eq!(
+        err.to_string(),
+        "value 300 (i32) out of range for u8 [0, 255]"
+    );
+
+    let err = (-1i8).value_as::<u32>().with_context().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "value -1 (i8) out of range for u32 [0, 4294967295]"
+    );
+
+    let err = f32::NAN.approx_as::<i16>().with_context().unwrap_err();
+    assert_eq!(err.kind, GeneralErrorKind::Unrepresentable);
+    assert_eq!(err.to_string(), "value NaN (f32) not representable in i16");
+
+    let err = 16_777_217i32.value_as::<f32>().with_context().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "value 16777217 (i32) out of range for f32 [-16777216, 16777216]"
+    );
+
+    assert_eq!(200i32.value_as::<u8>().with_context(), Ok(200u8));
+    assert_eq!(7u8.value_as::<u16>().with_context().unwrap_ok(), 7u16);
+}
+
+#[test]
+fn test_with_context_question_mark() {
+    fn narrow(v: i64) -> Result<u16, Box<dyn std::error::Error>> {
+        Ok(v.value_as::<u16>().with_context()?)
+    }
+
+    assert_eq!(narrow(1).unwrap(), 1);
+    assert_eq!(
+        narrow(-5).unwrap_err().to_string(),
+        "value -5 (i64) out of range for u16 [0, 65535]"
+    );
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(
+        GeneralError::PosOverflow(256.5f32).to_string(),
+        "conversion resulted in positive overflow: 256.5"
+    );
+    assert_eq!(
+        format!("{:#}", GeneralError::NegOverflow(-1i8)),
+        "NegOverflow: conversion resulted in negative overflow: -1"
+    );
+    assert_eq!(
+        format!("{:#}", GeneralError::Unrepresentable(7u8)),
+        "Unrepresentable: could not convert unrepresentable value: 7"
+    );
+
+    assert_eq!(
+        FloatError::NotANumber(f64::NAN).to_string(),
+        "conversion target does not support not-a-number: NaN"
+    );
+    assert_eq!(
+        format!("{:#}", FloatError::PosOverflow(1e10f64)),
+        "PosOverflow: conversion resulted in positive overflow: 10000000000.0"
+    );
+
+    assert_eq!(
+        RangeError::NegOverflow(-3i32).to_string(),
+        "conversion resulted in negative overflow: -3"
+    );
+    assert_eq!(
+        format!("{:#}", RangeError::PosOverflow(300u16)),
+        "PosOverflow: conversion resulted in positive overflow: 300"
+    );
+
+    assert_eq!(
+        NegOverflow(-1i64).to_string(),
+        "conversion resulted in negative overflow: -1"
+    );
+    assert_eq!(
+        format!("{:#}", PosOverflow(9u8)),
+        "PosOverflow: conversion resulted in positive overflow: 9"
+    );
+    assert_eq!(
+        format!("{:#}", Unrepresentable(0.5f32)),
+        "Unrepresentable: could not convert unrepresentable value: 0.5"
+    );
+
+    assert_eq!(
+        GeneralErrorKind::PosOverflow.to_string(),
+        "conversion resulted in positive overflow"
+    );
+    assert_eq!(
+        format!("{:#}", GeneralErrorKind::Unrepresentable),
+        "Unrepresentable: could not convert unrepresentable value"
+    );
+    assert_eq!(
+        format!("{:#}", RangeErrorKind::NegOverflow),
+        "NegOverflow: conversion resulted in negative overflow"
+    );
+}
+
+#[test]
+fn test_payload_without_display() {
+    fn assert_error<E: std::error::Error>(_: &E) {}
+
+    // Tuples implement `Debug` but not `Display`; the payload is written with
+    // its `Debug` formatting.
+    let e = GeneralError::PosOverflow((1u8, 2u8));
+    assert_error(&e);
+    assert_eq!(
+        e.to_string(),
+        "conversion resulted in positive overflow: (1, 2)"
+    );
+    assert_error(&RangeError::NegOverflow((1u8, 2u8)));
+    assert_error(&FloatError::NotANumber(((), 0.5f32)));
+    assert_error(&NegOverflow([1u8]));
+    assert_error(&PosOverflow(vec![1u8]));
+    assert_error(&Unrepresentable(std::io::SeekFrom::Current(0)));
+
+    let boxed: Box<dyn std::error::Error> = Box::new(e);
+    assert_eq!(
+        boxed.to_string(),
+        "conversion resulted in positive overflow: (1, 2)"
+    );
+}
+
+#[test]
+fn test_debug() {
+    assert_eq!(
+        format!("{:?}", GeneralError::PosOverflow(256u32)),
+        "PosOverflow(256)"
+    );
+    assert_eq!(
+        format!("{:?}", FloatError::NotANumber(1.5f32)),
+        "NotANumber(1.5)"
+    );
+    assert_eq!(format!("{:?}", NegOverflow(-1i8)), "NegOverflow(-1)");
+
+    // `GeneralError` is usable as a boxed error.
+    let err: Box<dyn std::error::Error> = Box::new(GeneralError::NegOverflow(-1i8));
+    assert_eq!(
+        err.to_string(),
+        "conversion resulted in negative overflow: -1"
+    );
+}
+
+#[test]
+fn test_conv_error() {
+    fn inspect<E>(e: E) -> (GeneralErrorKind, E::Payload)
+    where
+        E: ConvError,
+    {
+        (e.kind(), e.into_inner())
+    }
+
+    assert_eq!(
+        inspect(GeneralError::Unrepresentable(1u8)),
+        (GeneralErrorKind::Unrepresentable, 1)
+    );
+    assert_eq!(
+        inspect(FloatError::NegOverflow(-1e10f64)),
+        (GeneralErrorKind::NegOverflow, -1e10)
+    );
+    assert_eq!(
+        inspect(RangeError::PosOverflow(300i32)),
+        (GeneralErrorKind::PosOverflow, 300)
+    );
+    assert_eq!(
+        inspect(NegOverflow(-1i8)),
+        (GeneralErrorKind::NegOverflow, -1)
+    );
+    assert_eq!(
+        inspect(PosOverflow(9u64)),
+        (GeneralErrorKind::PosOverflow, 9)
+    );
+    assert_eq!(
+        inspect(Unrepresentable(2i16)),
+        (GeneralErrorKind::Unrepresentable, 2)
+    );
+    assert_eq!(
+        inspect(GeneralErrorKind::PosOverflow),
+        (GeneralErrorKind::PosOverflow, ())
+    );
+    assert_eq!(
+        inspect(RangeErrorKind::NegOverflow),
+        (GeneralErrorKind::NegOverflow, ())
+    );
+
+    let err = ConversionError::new::<u8, _, _>(PosOverflow(256u16));
+    assert_eq!(err.as_inner(), "256");
+    assert_eq!(
+        inspect(err),
+        (GeneralErrorKind::PosOverflow, "256".to_string())
+    );
+
+    let err = 300i32.value_as::<u8>().with_context().unwrap_err();
+    assert_eq!(*err.as_inner(), 300);
+    assert_eq!(ConvError::kind(&err), GeneralErrorKind::PosOverflow);
+
+    let err = FloatError::NotANumber(f32::NAN);
+    assert!(err.as_inner().is_nan());
+}
+
+#[test]
+fn test_map_payload() {
+    assert_eq!(
+        GeneralError::PosOverflow(1u8).map_payload(u16::from),
+        GeneralError::PosOverflow(1u16)
+    );
+    assert_eq!(
+        FloatError::NotANumber(1.5f32).map_payload(f64::from),
+        FloatError::NotANumber(1.5f64)
+    );
+    assert_eq!(
+        RangeError::NegOverflow(-1i32).map_payload(|v| v.to_string()),
+        RangeError::NegOverflow("-1".to_string())
+    );
+    assert_eq!(NegOverflow(-1i8).map_payload(i64::from), NegOverflow(-1i64));
+    assert_eq!(PosOverflow(2u8).map_payload(|v| v * 2), PosOverflow(4u8));
+    assert_eq!(
+        Unrepresentable(3u8).map_payload(|v| [v]),
+        Unrepresentable([3u8])
+    );
+
+    let err = (-5i64).value_as::<u16>().with_context().unwrap_err();
+    let err = err.map_payload(|v| v.abs());
+    assert_eq!(err.value, 5);
+    assert_eq!(err.max, u16::MAX);
+
+    // Generic code can transform errors without knowing their shape.
+    fn stringify<E>(e: E) -> E::Output
+    where
+        E: MapPayload<String>,
+        E::Payload: std::fmt::Display,
+    {
+        e.map_payload(|v| v.to_string())
+    }
+    assert_eq!(
+        stringify(FloatError::PosOverflow(256.5f32)),
+        FloatError::PosOverflow("256.5".to_string())
+    );
+}
@@ -0,0 +1,243 @@
+#![cfg(feature = "fixed")]
+
+use conv2::*;
+use fixed::types::{I0F8, I16F16, I32F32, I8F8, U0F128, U16F16, U8F8};
+use fixed::{FixedI128, FixedI8};
+
+type I128F0 = FixedI128<fixed::types::extra::U0>;
+type I0F128 = FixedI128<fixed::types::extra::U128>;
+type I8F0 = FixedI8<fixed::types::extra::U0>;
+
+macro_rules! approx {
+    ($dst:ty, $scheme:ty, $src:expr) => {
+        <$dst as ApproxFrom<_, $scheme>>::approx_from($src)
+    };
+}
+
+#[test]
+fn test_int_to_fixed() {
+    assert_eq!(
+        I16F16::value_from(-32_768i64),
+        Ok(I16F16::from_num(-32_768))
+    );
+    assert_eq!(
+        I16F16::value_from(32_768i64),
+        Err(RangeError::PosOverflow(32_768))
+    );
+    assert_eq!(U8F8::value_from(-1i8), Err(RangeError::NegOverflow(-1)));
+    assert_eq!(I0F8::value_from(0u64), Ok(I0F8::ZERO));
+    assert_eq!(I0F8::value_from(1u64), Err(RangeError::PosOverflow(1)));
+    assert_eq!(I128F0::value_from(u64::MAX), Ok(I128F0::from_num(u64::MAX)));
+}
+
+#[test]
+fn test_fixed_to_int() {
+    for (src, nearest, half_even, zero, neg_inf, pos_inf, wrapping) in [
+        (2.5, 3, 2, 2, 2, 3, 2),
+        (3.5, 4, 4, 3, 3, 4, 3),
+        (-2.5, -3, -2, -2, -3, -2, -3),
+        (2.75, 3, 3, 2, 2, 3, 2),
+        (-2.25, -2, -2, -2, -3, -2, -3),
+        (7.0, 7, 7, 7, 7, 7, 7),
+    ] {
+        let src = I16F16::from_num(src);
+        assert_eq!(approx!(i32, RoundToNearest, src), Ok(nearest));
+        assert_eq!(approx!(i32, RoundHalfEven, src), Ok(half_even));
+        assert_eq!(approx!(i32, RoundToZero, src), Ok(zero));
+        assert_eq!(approx!(i32, RoundToNegInf, src), Ok(neg_inf));
+        assert_eq!(approx!(i32, RoundToPosInf, src), Ok(pos_inf));
+        assert_eq!(approx!(i32, DefaultApprox, src), Ok(zero));
+        assert_eq!(approx!(i32, Wrapping, src), Ok(wrapping));
+    }
+
+    // Rounding can produce a value just outside the fixed-point range.
+    let max = I8F8::MAX;
+    assert_eq!(approx!(i16, RoundToPosInf, max), Ok(128));
+    assert_eq!(
+        approx!(i8, RoundToPosInf, max),
+        Err(RangeError::PosOverflow(max))
+    );
+    assert_eq!(approx!(i8, RoundToZero, max), Ok(127));
+    assert_eq!(approx!(u8, Wrapping, I8F8::from_num(-1)), Ok(255));
+    assert_eq!(
+        approx!(u8, RoundToNegInf, I8F8::from_num(-0.5)),
+        Err(RangeError::NegOverflow(I8F8::from_num(-0.5)))
+    );
+    assert_eq!(approx!(u8, RoundToZero, I8F8::from_num(-0.5)), Ok(0));
+
+    // Types with no fractional bits, or no integer bits.
+    assert_eq!(approx!(i64, RoundToNearest, I8F0::MIN), Ok(-128));
+    assert_eq!(approx!(i8, RoundToNearest, I0F8::from_num(-0.5)), Ok(-1));
+    assert_eq!(approx!(i8, RoundHalfEven, I0F8::from_num(-0.5)), Ok(0));
+    assert_eq!(approx!(u8, RoundToNearest, U0F128::MAX), Ok(1));
+    assert_eq!(approx!(u8, RoundToZero, U0F128::MAX), Ok(0));
+    assert_eq!(approx!(i8, RoundToNearest, I0F128::MIN), Ok(-1));
+    assert_eq!(approx!(i8, RoundToPosInf, I0F128::MIN), Ok(0));
+    assert_eq!(approx!(i8, RoundToNegInf, I0F128::MIN), Ok(-1));
+    assert_eq!(
+        approx!(u64, DefaultApprox, I128F0::MAX),
+        Err(RangeError::PosOverflow(I128F0::MAX))
+    );
+}
+
+#[test]
+fn test_fixed_value_to_int() {
+    assert_eq!(i32::value_from(I16F16::from_num(-7)), Ok(-7));
+    assert_eq!(u8::value_from(U8F8::MAX - U8F8::frac(U8F8::MAX)), Ok(255));
+    assert_eq!(i8::value_from(I8F0::MIN), Ok(-128));
+    assert_eq!(u64::value_from(I0F8::ZERO), Ok(0));
+    assert_eq!(u64::value_from(I128F0::from_num(u64::MAX)), Ok(u64::MAX));
+
+    // Fractional values are rejected, whatever the destination.
+    let half = I16F16::from_num(-2.5);
+    assert_eq!(
+        i32::value_from(half),
+        Err(GeneralError::Unrepresentable(half))
+    );
+    assert_eq!(
+        u8::value_from(half),
+        Err(GeneralError::Unrepresentable(half))
+    );
+    assert_eq!(
+        u64::value_from(U0F128::DELTA),
+        Err(GeneralError::Unrepresentable(U0F128::DELTA))
+    );
+
+    // Integral values must fit.
+    let big = I16F16::from_num(300);
+    assert_eq!(u8::value_from(big), Err(GeneralError::PosOverflow(big)));
+    assert_eq!(u16::value_from(-big), Err(GeneralError::NegOverflow(-big)));
+    assert_eq!(
+        i64::value_from(I128F0::MIN),
+        Err(GeneralError::NegOverflow(I128F0::MIN))
+    );
+}
+
+#[test]
+fn test_fixed_to_float() {
+    assert_eq!(f32::value_from(I16F16::from_num(-1.25)), Ok(-1.25));
+    assert_eq!(f64::value_from(I32F32::DELTA), Ok(2f64.powi(-32)));
+    assert_eq!(
+        f32::value_from(I32F32::MAX),
+        Err(Unrepresentable(I32F32::MAX))
+    );
+    assert_eq!(
+        f64::value_from(I128F0::from_num((1u64 << 53) + 1)),
+        Err(Unrepresentable(I128F0::from_num((1u64 << 53) + 1)))
+    );
+    // 2^-128 is subnormal as an `f32`.
+    assert_eq!(f32::value_from(U0F128::DELTA), Ok(f32::from_bits(1 << 21)));
+
+    assert_eq!(
+        approx!(f32, DefaultApprox, I32F32::MAX),
+        Ok(2_147_483_648.0)
+    );
+}
+
+#[test]
+fn test_float_value_to_fixed() {
+    assert_eq!(U8F8::value_from(1.25f32), Ok(U8F8::from_num(1.25)));
+    assert_eq!(I8F8::value_from(-128.0f64), Ok(I8F8::MIN));
+    assert_eq!(I32F32::value_from(2f64.powi(-32)), Ok(I32F32::DELTA));
+    assert_eq!(I128F0::value_from(-2f64.powi(127)), Ok(I128F0::MIN));
+
+    // Fractional bits beyond the destination's precision.
+    assert_eq!(
+        U8F8::value_from(0.1f64),
+        Err(GeneralError::Unrepresentable(0.1))
+    );
+    assert_eq!(
+        I8F0::value_from(0.5f32),
+        Err(GeneralError::Unrepresentable(0.5))
+    );
+    assert!(matches!(
+        U16F16::value_from(f32::NAN),
+        Err(GeneralError::Unrepresentable(v)) if v.is_nan()
+    ));
+
+    // Overflow, including values that would also be fractional.
+    assert_eq!(
+        U8F8::value_from(256.0f32),
+        Err(GeneralError::PosOverflow(256.0))
+    );
+    assert_eq!(
+        U8F8::value_from(-0.5f32),
+        Err(GeneralError::NegOverflow(-0.5))
+    );
+    assert_eq!(
+        I128F0::value_from(2f64.powi(127)),
+        Err(GeneralError::PosOverflow(2f64.powi(127)))
+    );
+    assert_eq!(
+        I16F16::value_from(f64::NEG_INFINITY),
+        Err(GeneralError::NegOverflow(f64::NEG_INFINITY))
+    );
+    assert_eq!(
+        I16F16::value_from(1e300f64),
+        Err(GeneralError::PosOverflow(1e300))
+    );
+}
+
+#[test]
+fn test_float_to_fixed() {
+    let delta = U8F8::DELTA;
+    let tenth = U8F8::from_bits(25);
+    assert_eq!(approx!(U8F8, RoundToNegInf, 0.1f64), Ok(tenth));
+    assert_eq!(approx!(U8F8, RoundToZero, 0.1f64), Ok(tenth));
+    assert_eq!(approx!(U8F8, RoundToPosInf, 0.1f64), Ok(tenth + delta));
+    assert_eq!(approx!(U8F8, RoundToNearest, 0.1f64), Ok(tenth + delta));
+    assert_eq!(
+        approx!(U8F8, DefaultApprox, 0.1f64),
+        Ok(U8F8::from_num(0.1))
+    );
+
+    // Ties at the fixed-point precision.
+    let tie = 2.5 / 256.0;
+    assert_eq!(approx!(U8F8, RoundToNearest, tie), Ok(U8F8::from_bits(3)));
+    assert_eq!(approx!(U8F8, RoundHalfEven, tie), Ok(U8F8::from_bits(2)));
+    assert_eq!(approx!(I8F8, RoundToNearest, -tie), Ok(I8F8::from_bits(-3)));
+    assert_eq!(approx!(I8F8, RoundHalfEven, -tie), Ok(I8F8::from_bits(-2)));
+    assert_eq!(approx!(I8F8, DefaultApprox, -tie), Ok(I8F8::from_bits(-2)));
+
+    // Range checks happen after rounding.
+    assert_eq!(approx!(U8F8, RoundToZero, 255.999f32), Ok(U8F8::MAX));
+    assert_eq!(
+        approx!(U8F8, RoundToPosInf, 255.999f32),
+        Err(FloatError::PosOverflow(255.999))
+    );
+    assert_eq!(approx!(U8F8, RoundToPosInf, -0.001f32), Ok(U8F8::ZERO));
+    assert_eq!(
+        approx!(U8F8, RoundToNegInf, -0.001f32),
+        Err(FloatError::NegOverflow(-0.001))
+    );
+    assert_eq!(approx!(I8F8, RoundToZero, -128.0f64), Ok(I8F8::MIN));
+    assert_eq!(
+        approx!(I128F0, RoundToZero, -2f64.powi(127)),
+        Ok(I128F0::MIN)
+    );
+    assert_eq!(
+        approx!(I128F0, RoundToZero, 2f64.powi(127)),
+        Err(FloatError::PosOverflow(2f64.powi(127)))
+    );
+
+    // Wrapping keeps the bits at the destination's precision.
+    assert_eq!(approx!(U8F8, Wrapping, 257.75f32), Ok(U8F8::from_num(1.75)));
+    assert_eq!(
+        approx!(U8F8, Wrapping, -0.25f64),
+        Ok(U8F8::from_num(255.75))
+    );
+    assert_eq!(approx!(I16F16, Wrapping, 1e300f64), Ok(I16F16::ZERO));
+
+    assert!(matches!(
+        approx!(U16F16, RoundToNearest, f32::NAN),
+        Err(FloatError::NotANumber(_))
+    ));
+    assert_eq!(
+        approx!(U16F16, Wrapping, f64::NEG_INFINITY),
+        Err(FloatError::NegOverflow(f64::NEG_INFINITY))
+    );
+    assert_eq!(
+        approx!(U16F16, DefaultApprox, f64::INFINITY),
+        Err(FloatError::PosOverflow(f64::INFINITY))
+    );
+}
@@ -0,0 +1,31 @@
+use conv2::prelude::*;
+use conv2::{assert_infallible, NoError};
+
+assert_infallible!(u8 => u16, i16 => i64, u32 => f64, f32 => f64);
+
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+assert_infallible!(usize => u64, u32 => usize);
+
+#[test]
+fn test_value_into_infallible() {
+    assert_eq!(200u8.value_into_infallible::<u16>(), 200);
+    assert_eq!((-5i8).value_into_infallible::<i64>(), -5);
+    let x: f64 = 1.5f32.value_into_infallible();
+    assert_eq!(x, 1.5);
+    assert_eq!(i64::infallible_from(-7i32), -7);
+}
+
+#[test]
+fn test_generic() {
+    fn widen<T, U: InfallibleFrom<T>>(values: Vec<T>) -> Vec<U> {
+        values.into_iter().map(U::infallible_from).collect()
+    }
+
+    assert_eq!(widen::<u8, u32>(vec![1, 2, 255]), vec![1u32, 2, 255]);
+
+    // `InfallibleFrom` implies `ValueFrom` with `NoError`.
+    fn checked<T, U: InfallibleFrom<T>>(value: T) -> Result<U, NoError> {
+        U::value_from(value)
+    }
+    assert_eq!(checked::<i16, i32>(-1), Ok(-1));
+}
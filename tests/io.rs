@@ -0,0 +1,149 @@
+use std::io::{ErrorKind, SeekFrom};
+
+use conv2::*;
+
+#[test]
+fn test_seek_from() {
+    assert_eq!(SeekFrom::value_from(0u8).unwrap_ok(), SeekFrom::Start(0));
+    assert_eq!(
+        SeekFrom::value_from(u64::MAX).unwrap_ok(),
+        SeekFrom::Start(u64::MAX)
+    );
+    assert_eq!(
+        SeekFrom::value_from(usize::MAX).unwrap_ok(),
+        SeekFrom::Start(usize::MAX as u64)
+    );
+
+    assert_eq!(SeekFrom::value_from(0i8), Ok(SeekFrom::Start(0)));
+    assert_eq!(
+        SeekFrom::value_from(i64::MAX),
+        Ok(SeekFrom::Start(i64::MAX as u64))
+    );
+    assert_eq!(SeekFrom::value_from(-1i32), Err(NegOverflow(-1)));
+    assert_eq!(
+        SeekFrom::value_from(isize::MIN),
+        Err(NegOverflow(isize::MIN))
+    );
+
+    assert_eq!(conv2::io::start_offset(SeekFrom::Start(7)).unwrap(), 7);
+    assert_eq!(
+        conv2::io::start_offset(SeekFrom::Current(7))
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidInput
+    );
+    assert_eq!(
+        conv2::io::start_offset(SeekFrom::End(-7))
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidInput
+    );
+}
+
+#[test]
+fn test_from_seek_from() {
+    // Absolute positions.
+    assert_eq!(u64::value_from(SeekFrom::Start(u64::MAX)), Ok(u64::MAX));
+    assert_eq!(i64::value_from(SeekFrom::Start(0)), Ok(0));
+    assert_eq!(
+        i64::value_from(SeekFrom::Start(i64::MAX as u64)),
+        Ok(i64::MAX)
+    );
+    assert_eq!(
+        i64::value_from(SeekFrom::Start(i64::MAX as u64 + 1)),
+        Err(GeneralError::PosOverflow(SeekFrom::Start(
+            i64::MAX as u64 + 1
+        )))
+    );
+
+    // Relative to the current position.
+    assert_eq!(isize::value_from(SeekFrom::Current(7)), Ok(7));
+    assert_eq!(i8::value_from(SeekFrom::Current(-128)), Ok(-128));
+    assert_eq!(
+        i8::value_from(SeekFrom::Current(-129)),
+        Err(GeneralError::NegOverflow(SeekFrom::Current(-129)))
+    );
+    assert_eq!(
+        u64::value_from(SeekFrom::Current(-3)),
+        Err(GeneralError::NegOverflow(SeekFrom::Current(-3)))
+    );
+
+    // Relative to the end.
+    assert_eq!(i64::value_from(SeekFrom::End(-7)), Ok(-7));
+    assert_eq!(u16::value_from(SeekFrom::End(65_535)), Ok(65_535));
+    assert_eq!(
+        u16::value_from(SeekFrom::End(65_536)),
+        Err(GeneralError::PosOverflow(SeekFrom::End(65_536)))
+    );
+    assert_eq!(
+        usize::value_from(SeekFrom::End(i64::MIN)),
+        Err(GeneralError::NegOverflow(SeekFrom::End(i64::MIN)))
+    );
+
+    assert_eq!(usize::value_from(SeekFrom::Start(4096)), Ok(4096));
+    assert_eq!(
+        u8::value_from(SeekFrom::Start(256)),
+        Err(GeneralError::PosOverflow(SeekFrom::Start(256)))
+    );
+
+    // Round trips through both directions.
+    for offset in [0i64, 1, 512, i64::MAX] {
+        let pos = SeekFrom::value_from(offset).unwrap();
+        assert_eq!(i64::value_from(pos), Ok(offset));
+    }
+    let pos: SeekFrom = 4096usize.value_into().unwrap_ok();
+    assert_eq!(pos.value_into(), Ok(4096usize));
+
+    // The errors can be used with `?` in functions returning `io::Result`.
+    fn offset(pos: SeekFrom) -> std::io::Result<i32> {
+        Ok(i32::value_from(pos)?)
+    }
+    assert_eq!(offset(SeekFrom::Start(9)).unwrap(), 9);
+    assert_eq!(offset(SeekFrom::End(-9)).unwrap(), -9);
+    assert_eq!(
+        offset(SeekFrom::End(i64::MAX)).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn test_lengths() {
+    use conv2::io::{isize_to_usize, u64_to_i64, u64_to_usize, usize_to_isize, usize_to_u64};
+
+    assert_eq!(u64_to_usize(0).unwrap(), 0);
+    assert_eq!(usize_to_u64(usize::MAX).unwrap(), usize::MAX as u64);
+    assert_eq!(isize_to_usize(isize::MAX).unwrap(), isize::MAX as usize);
+    assert_eq!(usize_to_isize(isize::MAX as usize).unwrap(), isize::MAX);
+    assert_eq!(u64_to_i64(i64::MAX as u64).unwrap(), i64::MAX);
+
+    let err = isize_to_usize(-4).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert_eq!(
+        err.to_string(),
+        "conversion resulted in negative overflow: -4 (isize to usize)"
+    );
+
+    let err = usize_to_isize(usize::MAX).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+    let err = u64_to_i64(u64::MAX).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert_eq!(
+        err.to_string(),
+        "conversion resulted in positive overflow: 18446744073709551615 (u64 to i64)"
+    );
+
+    #[cfg(target_pointer_width = "32")]
+    assert_eq!(
+        u64_to_usize(u64::MAX).unwrap_err().kind(),
+        ErrorKind::InvalidInput
+    );
+}
+
+#[test]
+fn test_generic() {
+    let n: u16 = conv2::io::value_into(300u64).unwrap();
+    assert_eq!(n, 300);
+    let err = conv2::io::value_into::<_, u8>(300u64).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
@@ -0,0 +1,73 @@
+use conv2::konst::Conv;
+use conv2::*;
+
+const BUF_SIZE: usize = 4096;
+const BUF_LEN: u16 = const_value_as!(usize => u16, BUF_SIZE);
+const SIGNED: i8 = Conv::<u64, i8>::value_as(127);
+const NEGATIVE: Result<u32, NegOverflow<i16>> = Conv::<i16, u32>::value_from(-5);
+
+#[test]
+fn test_const_context() {
+    assert_eq!(BUF_LEN, 4096);
+    assert_eq!(SIGNED, 127);
+    assert_eq!(NEGATIVE, Err(NegOverflow(-5)));
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(Conv::<u8, u16>::value_from(255), Ok(255));
+    assert_eq!(
+        Conv::<i32, i8>::value_from(-129),
+        Err(RangeError::NegOverflow(-129))
+    );
+    assert_eq!(
+        Conv::<i32, i8>::value_from(128),
+        Err(RangeError::PosOverflow(128))
+    );
+    assert_eq!(
+        Conv::<u32, i16>::value_from(40_000),
+        Err(PosOverflow(40_000))
+    );
+    assert_eq!(Conv::<isize, usize>::value_from(-1), Err(NegOverflow(-1)));
+    assert_eq!(
+        Conv::<i64, u8>::value_from(-1),
+        Err(RangeError::NegOverflow(-1))
+    );
+}
+
+#[test]
+#[should_panic(expected = "value out of range")]
+fn test_value_as_panics() {
+    let big = 70_000u32;
+    Conv::<u32, u16>::value_as(big);
+}
+
+macro_rules! check_same {
+    ($($src:ident => $($dst:ident),*;)*) => {
+        $($(
+            for v in [$src::MIN, $src::MIN / 2, 0, 1, $src::MAX / 2, $src::MAX] {
+                assert_eq!(
+                    Conv::<$src, $dst>::value_from(v),
+                    <$dst as ValueFrom<$src>>::value_from(v),
+                    "{} -> {}: {}", stringify!($src), stringify!($dst), v
+                );
+            }
+        )*)*
+    };
+}
+
+#[test]
+fn test_matches_value_from() {
+    check_same! {
+        i8 => i16, i32, i64, isize, u8, u16, u32, u64, usize;
+        i16 => i8, i32, i64, isize, u8, u16, u32, u64, usize;
+        i32 => i8, i16, i64, isize, u8, u16, u32, u64, usize;
+        i64 => i8, i16, i32, isize, u8, u16, u32, u64, usize;
+        isize => i8, i16, i32, i64, u8, u16, u32, u64, usize;
+        u8 => i8, i16, i32, i64, isize, u16, u32, u64, usize;
+        u16 => i8, i16, i32, i64, isize, u8, u32, u64, usize;
+        u32 => i8, i16, i32, i64, isize, u8, u16, u64, usize;
+        u64 => i8, i16, i32, i64, isize, u8, u16, u32, usize;
+        usize => i8, i16, i32, i64, isize, u8, u16, u32, u64;
+    }
+}
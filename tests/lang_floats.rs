@@ -143,7 +143,15 @@ fn step64(x: f64, mut step: f64) -> f64 {
 
 #[test]
 fn test_limits() {
-    use conv2::{MAX_F32_I32, MAX_F32_I64, MIN_F32_I32, MIN_F32_I64};
+    const MAX_F32_I32: f32 = SafeRange::<f32, i32>::MAX;
+    const MIN_F32_I32: f32 = SafeRange::<f32, i32>::MIN;
+    const MAX_F32_I64: f32 = SafeRange::<f32, i64>::MAX;
+    const MIN_F32_I64: f32 = SafeRange::<f32, i64>::MIN;
+    const MAX_F32_U32: f32 = SafeRange::<f32, u32>::MAX;
+    const MAX_F32_U64: f32 = SafeRange::<f32, u64>::MAX;
+    const MAX_F64_I64: f64 = SafeRange::<f64, i64>::MAX;
+    const MIN_F64_I64: f64 = SafeRange::<f64, i64>::MIN;
+    const MAX_F64_U64: f64 = SafeRange::<f64, u64>::MAX;
 
     // Verify that the min and max values we use are the actual limits:
     // they succeed, and anything further from zero will fail.
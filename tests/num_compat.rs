@@ -0,0 +1,182 @@
+#![cfg(feature = "num-traits")]
+
+use conv2::misc::Saturated;
+use conv2::num_compat::{cast, Num};
+use conv2::*;
+
+#[test]
+fn test_value_from_num() {
+    assert_eq!(u8::value_from(Num(255u64)), Ok(255));
+    assert_eq!(
+        u8::value_from(Num(256u64)),
+        Err(GeneralError::PosOverflow(256))
+    );
+    assert_eq!(
+        u8::value_from(Num(-1i64)),
+        Err(GeneralError::NegOverflow(-1))
+    );
+    assert_eq!(i64::value_from(Num(-3.0f64)), Ok(-3));
+    assert_eq!(
+        i64::value_from(Num(-3.5f64)),
+        Err(GeneralError::Unrepresentable(-3.5))
+    );
+    assert_eq!(
+        f32::value_from(Num(16_777_217u32)),
+        Err(GeneralError::Unrepresentable(16_777_217))
+    );
+    assert!(f32::value_from(Num(f64::NAN)).unwrap().is_nan());
+    assert!(matches!(
+        u32::value_from(Num(f64::NAN)),
+        Err(GeneralError::Unrepresentable(v)) if v.is_nan()
+    ));
+}
+
+#[test]
+fn test_value_into_num() {
+    assert_eq!(Num::<u16>::value_from(65_535i32), Ok(Num(65_535)));
+    assert_eq!(
+        Num::<u16>::value_from(65_536i32),
+        Err(GeneralError::PosOverflow(65_536))
+    );
+    assert_eq!(
+        Num::<u16>::value_from(-1i32),
+        Err(GeneralError::NegOverflow(-1))
+    );
+    assert_eq!(
+        Num::<i32>::value_from(0.5f32),
+        Err(GeneralError::Unrepresentable(0.5))
+    );
+    assert_eq!(
+        Num::<f64>::value_from(u64::MAX),
+        Err(GeneralError::Unrepresentable(u64::MAX))
+    );
+    assert_eq!(
+        Num::<f64>::value_from(1u64 << 53),
+        Ok(Num(9_007_199_254_740_992.0))
+    );
+}
+
+#[test]
+fn test_approx() {
+    assert_eq!(Num(255.9f32).approx_as::<u8>(), Ok(255));
+    assert_eq!(Num(-0.9f32).approx_as_by::<u8, RoundToZero>(), Ok(0));
+    assert_eq!(
+        Num(256.0f32).approx_as::<u8>(),
+        Err(GeneralError::PosOverflow(256.0))
+    );
+    assert_eq!(
+        Num(-1.0f64).approx_as::<u8>(),
+        Err(GeneralError::NegOverflow(-1.0))
+    );
+    assert!(matches!(
+        Num(f32::NAN).approx_as::<i8>(),
+        Err(GeneralError::Unrepresentable(_))
+    ));
+
+    assert_eq!(2.75f64.approx_as::<Num<i16>>(), Ok(Num(2)));
+    assert_eq!(
+        1e10f64.approx_as::<Num<i16>>(),
+        Err(GeneralError::PosOverflow(1e10))
+    );
+    assert_eq!(
+        (-1e10f64).approx_as_by::<Num<i16>, RoundToZero>(),
+        Err(GeneralError::NegOverflow(-1e10))
+    );
+}
+
+#[test]
+fn test_saturated() {
+    assert_eq!(Num::<i8>::saturated_max(), Num(127));
+    assert_eq!(Num::<i8>::saturated_min(), Num(-128));
+}
+
+#[test]
+fn test_cast() {
+    // Matches `num_traits::cast` for the cases both support.
+    fn check<Src, Dst>(src: Src)
+    where
+        Src: num_traits::NumCast + Copy,
+        Dst: num_traits::NumCast + ApproxFrom<Src> + PartialEq + std::fmt::Debug,
+    {
+        assert_eq!(cast::<Src, Dst>(src), num_traits::cast::<Src, Dst>(src));
+    }
+
+    check::<f32, u8>(255.9);
+    check::<f32, u8>(256.0);
+    check::<f32, u8>(-1.0);
+    check::<f32, i32>(f32::NAN);
+    check::<f64, i64>(-9.3e18);
+    check::<i32, u16>(-1);
+    check::<i32, u16>(65_535);
+    check::<u64, i8>(128);
+    check::<u64, f32>(u64::MAX);
+}
+
+#[test]
+fn test_float_overflow() {
+    // Matches the builtin `f64` to `f32` conversion.
+    assert_eq!(
+        Num(1e300f64).approx_as::<f32>(),
+        Err(GeneralError::PosOverflow(1e300))
+    );
+    assert_eq!(
+        Num(-1e300f64).approx_as::<f32>(),
+        Err(GeneralError::NegOverflow(-1e300))
+    );
+    assert!(1e300f64.approx_as::<f32>().is_err());
+    assert_eq!(Num(f64::INFINITY).approx_as::<f32>(), Ok(f32::INFINITY));
+    assert_eq!(Num(1.5f64).approx_as::<f32>(), Ok(1.5));
+    assert!(Num(f64::NAN).approx_as::<f32>().unwrap().is_nan());
+
+    assert_eq!(
+        1e300f64.approx_as::<Num<f32>>(),
+        Err(GeneralError::PosOverflow(1e300))
+    );
+    assert_eq!(
+        f64::NEG_INFINITY.approx_as::<Num<f32>>(),
+        Ok(Num(f32::NEG_INFINITY))
+    );
+}
+
+#[test]
+fn test_num_traits() {
+    use num_traits::{FromPrimitive, NumCast, ToPrimitive};
+
+    assert_eq!(Num(255u32).to_u8(), Some(255));
+    assert_eq!(Num(256u32).to_u8(), None);
+    assert_eq!(Num(-1i64).to_u64(), None);
+    assert_eq!(Num(2.5f64).to_i32(), None);
+    assert_eq!(Num(2.0f64).to_i32(), Some(2));
+    assert_eq!(Num(1e300f64).to_f32(), None);
+
+    assert_eq!(Num::<u8>::from_i64(255), Some(Num(255)));
+    assert_eq!(Num::<u8>::from_i64(-1), None);
+    assert_eq!(Num::<i32>::from_f64(0.5), None);
+
+    assert_eq!(<Num<u8> as NumCast>::from(200i64), Some(Num(200)));
+    assert_eq!(<Num<u8> as NumCast>::from(300i64), None);
+    assert_eq!(<Num<u64> as NumCast>::from(u64::MAX), Some(Num(u64::MAX)));
+    assert_eq!(<Num<i16> as NumCast>::from(-7.0f32), Some(Num(-7)));
+    assert_eq!(<Num<i16> as NumCast>::from(-7.5f32), None);
+    assert_eq!(<Num<f64> as NumCast>::from(-7.5f32), Some(Num(-7.5)));
+    assert_eq!(<Num<f32> as NumCast>::from(1e300f64), None);
+    assert_eq!(<Num<f64> as NumCast>::from(1e300f64), Some(Num(1e300)));
+
+    // Wide integers are not rounded through `f64`.
+    let wide = (1u128 << 100) + 1;
+    assert_eq!(<Num<f64> as NumCast>::from(wide), None);
+    assert_eq!(<Num<f64> as NumCast>::from(-(wide as i128)), None);
+    assert_eq!(<Num<u128> as NumCast>::from(wide), Some(Num(wide)));
+    assert_eq!(
+        <Num<i128> as NumCast>::from(i128::MIN),
+        Some(Num(i128::MIN))
+    );
+    assert_eq!(<Num<u64> as NumCast>::from(wide), None);
+    assert_eq!(Num(wide).to_u128(), Some(wide));
+    assert_eq!(Num(wide).to_i64(), None);
+    assert_eq!(u128::value_from(Num(wide)), Ok(wide));
+
+    // Generic code written against `num-traits` gets the checks too.
+    assert_eq!(num_traits::cast::<_, Num<u8>>(Num(300u16)), None);
+    assert_eq!(num_traits::cast::<_, Num<u8>>(Num(30u16)), Some(Num(30)));
+}
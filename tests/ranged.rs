@@ -0,0 +1,182 @@
+use conv2::bounded::Bounded;
+use conv2::ranged::{Ranged, RangedI16, RangedI32, RangedI64, RangedI8, RangedU16, RangedU8};
+use conv2::*;
+
+type Month = RangedU8<1, 12>;
+type DayOfYear = RangedI32<1, 366>;
+type Offset = RangedI8<-10, 10>;
+
+#[test]
+fn test_new() {
+    assert_eq!(Month::new(1).map(Month::get), Some(1));
+    assert_eq!(Month::new(12).map(Month::get), Some(12));
+    assert_eq!(Month::new(0), None);
+    assert_eq!(Month::new(13), None);
+    assert_eq!(Offset::new(-11), None);
+
+    assert_eq!(Month::MIN, 1);
+    assert_eq!(Month::MAX, 12);
+    assert_eq!(<Offset as Ranged>::LOWER, -10);
+    assert_eq!(<Offset as Ranged>::UPPER, 10);
+    assert_eq!(RangedI64::<{ i64::MIN }, 0>::MIN, i64::MIN);
+
+    const M: Option<Month> = Month::new(6);
+    assert!(M.is_some());
+
+    assert_eq!(Offset::new(-3).unwrap().to_string(), "-3");
+}
+
+#[test]
+fn test_from_primitive() {
+    assert_eq!(Month::value_from(7i64).map(Month::get), Ok(7));
+    assert_eq!(Month::value_from(13u32), Err(RangeError::PosOverflow(13)));
+    assert_eq!(Month::value_from(0usize), Err(RangeError::NegOverflow(0)));
+
+    // Failures of the underlying conversion are reported the same way.
+    assert_eq!(Month::value_from(-1i8), Err(RangeError::NegOverflow(-1)));
+    assert_eq!(
+        Month::value_from(1000i32),
+        Err(RangeError::PosOverflow(1000))
+    );
+    assert_eq!(Offset::value_from(200u8), Err(RangeError::PosOverflow(200)));
+
+    assert_eq!(Offset::value_from(-10i64).map(Offset::get), Ok(-10));
+    assert_eq!(
+        Offset::value_from(-11i16),
+        Err(RangeError::NegOverflow(-11))
+    );
+
+    let d: Result<DayOfYear, _> = 366u16.value_into();
+    assert_eq!(d.map(DayOfYear::get), Ok(366));
+}
+
+#[test]
+fn test_into_primitive() {
+    let m = Month::new(7).unwrap();
+    let x: Result<u8, NoError> = m.value_into();
+    assert_eq!(x, Ok(7));
+    let x: Result<i64, NoError> = m.value_into();
+    assert_eq!(x, Ok(7));
+    let x: Result<i8, PosOverflow<u8>> = m.value_into();
+    assert_eq!(x, Ok(7));
+
+    // The error type depends only on the underlying type.
+    let o = Offset::new(-5).unwrap();
+    assert_eq!(u8::value_from(o), Err(NegOverflow(-5)));
+    assert_eq!(i32::value_from(o), Ok(-5));
+}
+
+#[test]
+fn test_widen_narrow() {
+    let m = Month::new(12).unwrap();
+    let d: DayOfYear = m.widen();
+    assert_eq!(d.get(), 12);
+    let x: RangedU8<0, 255> = m.widen();
+    assert_eq!(x.get(), 12);
+    let o: RangedI16<-10, 10> = Offset::new(-10).unwrap().widen();
+    assert_eq!(o.get(), -10);
+
+    let d = DayOfYear::new(100).unwrap();
+    assert_eq!(d.narrow::<Month>(), Err(RangeError::PosOverflow(d)));
+    let d = DayOfYear::new(5).unwrap();
+    assert_eq!(d.narrow::<Month>().map(Month::get), Ok(5));
+    let o = Offset::new(-1).unwrap();
+    assert_eq!(
+        o.narrow::<RangedU16<0, 9>>(),
+        Err(RangeError::NegOverflow(o))
+    );
+
+    assert_eq!(
+        DayOfYear::new(13).unwrap().narrow::<RangedI64<1, 12>>(),
+        Err(RangeError::PosOverflow(DayOfYear::new(13).unwrap()))
+    );
+}
+
+#[test]
+fn test_value_from_ranged() {
+    // Between different underlying types, nested ranges convert with
+    // `NoError`.
+    let m = Month::new(12).unwrap();
+    let d: Result<DayOfYear, NoError> = m.value_into();
+    assert_eq!(d.map(DayOfYear::get), Ok(12));
+    let o = Offset::new(-10).unwrap();
+    assert_eq!(RangedI64::<-10, 10>::value_from(o).unwrap_ok().get(), -10);
+    assert_eq!(
+        RangedI16::<-200, 10>::infallible_from(Offset::new(-1).unwrap()).get(),
+        -1
+    );
+
+    // Identical ranges are covered by the reflexive implementation.
+    assert_eq!(Month::value_from(m), Ok(m));
+}
+
+#[test]
+fn test_bounded() {
+    type Percent = Bounded<u8, 0, 100>;
+
+    let m = Month::new(9).unwrap();
+    let p: Result<Percent, NoError> = m.value_into();
+    assert_eq!(p.map(Percent::get), Ok(9));
+    let p = Percent::new(42).unwrap();
+    let x: Result<RangedI16<-100, 100>, NoError> = p.value_into();
+    assert_eq!(x.map(|x| x.get()), Ok(42));
+    let x: RangedU8<0, 100> = p.widen();
+    assert_eq!(x.get(), 42);
+
+    assert_eq!(p.narrow::<Month>(), Err(RangeError::PosOverflow(p)));
+    assert_eq!(
+        Percent::new(7).unwrap().narrow::<Month>().map(Month::get),
+        Ok(7)
+    );
+    assert_eq!(
+        Offset::new(-1).unwrap().narrow::<Percent>(),
+        Err(RangeError::NegOverflow(Offset::new(-1).unwrap()))
+    );
+}
+
+#[test]
+fn test_approx_from() {
+    assert_eq!(11.9f32.approx_as::<Month>().map(Month::get), Ok(11));
+    assert_eq!(
+        13.0f64.approx_as::<Month>(),
+        Err(FloatError::PosOverflow(13.0))
+    );
+    assert_eq!(
+        0.5f64.approx_as::<Month>(),
+        Err(FloatError::NegOverflow(0.5))
+    );
+    assert!(matches!(
+        f32::NAN.approx_as::<Month>(),
+        Err(FloatError::NotANumber(_))
+    ));
+    let m: Result<Month, _> = 12.4f64.approx_by::<RoundToNearest>();
+    assert_eq!(m.map(Month::get), Ok(12));
+    let o: Result<Offset, _> = (-10.5f32).approx_by::<RoundToNegInf>();
+    assert_eq!(o, Err(FloatError::NegOverflow(-10.5)));
+    assert_eq!(
+        1e10f64.approx_as::<Month>().saturate().map(Month::get),
+        Ok(12)
+    );
+
+    // Integers convert as with `ValueFrom`.
+    assert_eq!(13i32.approx_as::<Month>(), Err(RangeError::PosOverflow(13)));
+    let o: Result<Offset, _> = 260i32.approx_by::<Wrapping>();
+    assert_eq!(o.map(Offset::get), Ok(4));
+    let o: Result<Offset, _> = 300i32.approx_by::<Wrapping>();
+    assert_eq!(o, Err(RangeError::PosOverflow(300)));
+}
+
+#[test]
+fn test_saturate() {
+    assert_eq!(Month::value_from(250u8).unwrap_or_saturate().get(), 12);
+    assert_eq!(Month::value_from(-5i32).unwrap_or_saturate().get(), 1);
+    assert_eq!(Offset::value_from(i64::MIN).unwrap_or_saturate().get(), -10);
+    assert_eq!(
+        DayOfYear::new(300)
+            .unwrap()
+            .narrow::<Month>()
+            .unwrap_or_saturate()
+            .get(),
+        12
+    );
+}
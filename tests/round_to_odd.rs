@@ -0,0 +1,135 @@
+use conv2::*;
+
+fn odd<Src, Dst>(src: Src) -> Dst
+where
+    Dst: ApproxFrom<Src, RoundToOdd>,
+    Dst::Err: std::fmt::Debug,
+{
+    Dst::approx_from(src).unwrap()
+}
+
+/// Rounds a normal `f64` to the nearest value with 11 significant bits (the
+/// precision of an IEEE half-precision float), with ties to even.
+fn round_to_half_precision(x: f64) -> f64 {
+    const DROPPED: u32 = f64::MANTISSA_DIGITS - 11;
+    let bits = x.to_bits();
+    let lsb = (bits >> DROPPED) & 1;
+    let rounded = (bits + (1 << (DROPPED - 1)) - 1 + lsb) & !((1 << DROPPED) - 1);
+    f64::from_bits(rounded)
+}
+
+#[test]
+fn test_int_to_float() {
+    // Exact values are unchanged.
+    assert_eq!(odd::<_, f32>(16_777_216i32), 16_777_216.0);
+    assert_eq!(odd::<_, f32>(16_777_218i32), 16_777_218.0);
+    assert_eq!(odd::<_, f32>(-7i8), -7.0);
+    assert_eq!(odd::<_, f64>(u32::MAX), u32::MAX as f64);
+    assert_eq!(odd::<_, f64>(i64::MIN), i64::MIN as f64);
+    assert_eq!(odd::<_, f32>(0u64), 0.0);
+
+    // Inexact values truncate, and end in an odd significand.
+    assert_eq!(odd::<_, f32>(16_777_217i32), 16_777_218.0);
+    assert_eq!(odd::<_, f32>(16_777_219u32), 16_777_218.0);
+    assert_eq!(odd::<_, f32>(16_777_221u64), 16_777_222.0);
+    assert_eq!(odd::<_, f32>(-16_777_217i64), -16_777_218.0);
+    assert_eq!(
+        odd::<_, f32>(u64::MAX),
+        f32::from_bits((127 + 63) << 23 | 0x7f_ffff)
+    );
+    assert_eq!(
+        odd::<_, f32>(i64::MAX),
+        f32::from_bits((127 + 62) << 23 | 0x7f_ffff)
+    );
+    assert_eq!(odd::<_, f64>(u64::MAX), 18_446_744_073_709_549_568.0);
+    assert_eq!(odd::<_, f64>((1u64 << 53) + 1), 9_007_199_254_740_994.0);
+    assert_eq!(odd::<_, f64>(-(1i64 << 53) - 1), -9_007_199_254_740_994.0);
+}
+
+#[test]
+fn test_f64_to_f32() {
+    let odd32 = |x: f64| -> f32 { odd(x) };
+
+    assert_eq!(odd32(1.5), 1.5);
+    assert_eq!(odd32(f32::MAX as f64), f32::MAX);
+    assert_eq!(odd32(f64::INFINITY), f32::INFINITY);
+    assert_eq!(odd32(f64::NEG_INFINITY), f32::NEG_INFINITY);
+    assert!(odd32(f64::NAN).is_nan());
+
+    // Values just above and below 1 round towards 1, then to odd.
+    let above = 1.0 + f32::EPSILON;
+    assert_eq!(odd32(1.0 + f64::EPSILON), above);
+    assert_eq!(odd32(1.0 + (f32::EPSILON as f64) * 0.9), above);
+    assert_eq!(odd32(-1.0 - f64::EPSILON), -above);
+    assert_eq!(odd32(1.0 - f64::EPSILON), 1.0 - f32::EPSILON / 2.0);
+
+    // Tiny values become the smallest subnormal, keeping their sign.
+    let tiny = f32::from_bits(1);
+    assert_eq!(odd32(1e-50), tiny);
+    assert_eq!(odd32(-1e-50), -tiny);
+
+    assert_eq!(
+        <f32 as ApproxFrom<f64, RoundToOdd>>::approx_from(f64::MAX),
+        Err(RangeError::PosOverflow(f64::MAX))
+    );
+    assert_eq!(
+        <f32 as ApproxFrom<f64, RoundToOdd>>::approx_from(f64::MIN),
+        Err(RangeError::NegOverflow(f64::MIN))
+    );
+
+    // `f32` → `f64` is exact for any scheme.
+    assert_eq!(odd::<f32, f64>(0.1), 0.1f32 as f64);
+}
+
+#[test]
+fn test_double_rounding_int() {
+    // Rounding twice to nearest loses the low bit, which decides the result.
+    let x = (1u64 << 60) + (1 << 36) + 1;
+    assert_ne!(x as f64 as f32, x as f32);
+    assert_eq!(odd::<_, f64>(x) as f32, x as f32);
+
+    let x = -(1i64 << 55) - (1 << 31) - 1;
+    assert_ne!(x as f64 as f32, x as f32);
+    assert_eq!(odd::<_, f64>(x) as f32, x as f32);
+
+    fn property_u64(x: u64) -> bool {
+        odd::<_, f64>(x) as f32 == x as f32
+    }
+    fn property_i64(x: i64) -> bool {
+        odd::<_, f64>(x) as f32 == x as f32
+    }
+    fn property_ties(sig: u32, low: u16, shift: u8) -> bool {
+        // Builds values whose 25th significant bit (the first one `f32` drops)
+        // is random, followed by zeros and then a few random low bits, which
+        // `f64` may drop in turn.
+        let sig = (sig as u64 & 0x1ff_ffff) | 1 << 24;
+        let x = sig << (11 + shift % 29) | low as u64;
+        property_u64(x) && property_i64(x as i64) && property_i64((x as i64).wrapping_neg())
+    }
+    quickcheck::quickcheck(property_u64 as fn(u64) -> bool);
+    quickcheck::quickcheck(property_i64 as fn(i64) -> bool);
+    quickcheck::quickcheck(property_ties as fn(u32, u16, u8) -> bool);
+}
+
+#[test]
+fn test_double_rounding_f64() {
+    // Narrowing f64 → f32 → half precision, compared with rounding directly.
+    let x = 1.0 + 2f64.powi(-11) + 2f64.powi(-40);
+    assert_ne!(
+        round_to_half_precision(x as f32 as f64),
+        round_to_half_precision(x)
+    );
+    assert_eq!(
+        round_to_half_precision(odd::<_, f32>(x) as f64),
+        round_to_half_precision(x)
+    );
+
+    fn property(sig: u64, exp: i8, negative: bool) -> bool {
+        // A normal value with a random significand, in the half-precision range.
+        let sig = sig & ((1 << 52) - 1);
+        let exp = (1023 + (exp % 15) as i64) as u64;
+        let x = f64::from_bits((negative as u64) << 63 | exp << 52 | sig);
+        round_to_half_precision(odd::<_, f32>(x) as f64) == round_to_half_precision(x)
+    }
+    quickcheck::quickcheck(property as fn(u64, i8, bool) -> bool);
+}
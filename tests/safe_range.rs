@@ -0,0 +1,115 @@
+use conv2::*;
+
+/// Returns the next representable value away from zero.
+macro_rules! outward {
+    ($fty:ident, $x:expr) => {{
+        let x: $fty = $x;
+        if x == 0.0 {
+            // The next value below `-0.0` is the smallest negative subnormal.
+            -$fty::from_bits(1)
+        } else {
+            $fty::from_bits(x.to_bits() + 1)
+        }
+    }};
+}
+
+macro_rules! check_bounds {
+    ($($fty:ident => $($ity:ident),*;)*) => {
+        $($(
+            check_bounds!(@ $fty => $ity: DefaultApprox, RoundToZero, RoundToNearest, RoundToNegInf, RoundToPosInf);
+        )*)*
+    };
+    (@ $fty:ident => $ity:ident: $($scheme:ident),*) => {
+        $({
+            let name = concat!(stringify!($fty), " -> ", stringify!($ity), " by ", stringify!($scheme));
+            let min = SafeRange::<$fty, $ity, $scheme>::MIN;
+            let max = SafeRange::<$fty, $ity, $scheme>::MAX;
+            let conv = |x: $fty| <$ity as ApproxFrom<$fty, $scheme>>::approx_from(x);
+
+            assert!(conv(min).is_ok(), "{}: min {}", name, min);
+            assert!(conv(max).is_ok(), "{}: max {}", name, max);
+            let below = outward!($fty, min);
+            let above = outward!($fty, max);
+            assert_eq!(conv(below), Err(FloatError::NegOverflow(below)), "{}", name);
+            assert_eq!(conv(above), Err(FloatError::PosOverflow(above)), "{}", name);
+        })*
+    };
+}
+
+#[test]
+fn test_float_to_int_bounds_are_exact() {
+    check_bounds! {
+        f32 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize;
+        f64 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize;
+    }
+}
+
+#[test]
+fn test_int_to_float() {
+    assert_eq!(SafeRange::<i64, f32>::MIN, i64::MIN);
+    assert_eq!(SafeRange::<u64, f64>::MAX, u64::MAX);
+    assert_eq!(
+        SafeRange::<u64, f32>::MAX.approx_as::<f32>(),
+        Ok(18_446_744_073_709_551_616.0)
+    );
+}
+
+#[test]
+fn test_clamp() {
+    // Clamping into the safe range means the conversion cannot fail.
+    for x in [-1e30f32, -300.0, -0.75, -0.5, 0.0, 0.5, 255.5, 256.0, 1e30] {
+        let clamped = x.clamp(
+            SafeRange::<f32, u8, RoundToNearest>::MIN,
+            SafeRange::<f32, u8, RoundToNearest>::MAX,
+        );
+        assert!(
+            clamped.approx_as_by::<u8, RoundToNearest>().is_ok(),
+            "{}",
+            x
+        );
+    }
+}
+
+#[test]
+fn test_unsigned_truncation() {
+    // Truncating into an unsigned type at least as wide as the float rejects
+    // every negative value; narrower types accept values above -1.0.
+    assert_eq!((-0.5f32).approx_as::<u8>(), Ok(0));
+    assert_eq!((-0.5f64).approx_as::<u32>(), Ok(0));
+    assert_eq!(
+        (-0.5f32).approx_as::<u32>(),
+        Err(FloatError::NegOverflow(-0.5))
+    );
+    assert_eq!(
+        (-0.5f32).approx_as_by::<u64, RoundToZero>(),
+        Err(FloatError::NegOverflow(-0.5))
+    );
+    assert_eq!(
+        (-0.5f64).approx_as::<u64>(),
+        Err(FloatError::NegOverflow(-0.5))
+    );
+    assert_eq!(
+        (-0.5f32).approx_as::<usize>(),
+        Err(FloatError::NegOverflow(-0.5))
+    );
+    assert_eq!((-0.0f32).approx_as::<u32>(), Ok(0));
+    assert_eq!((-0.0f64).approx_as::<u64>(), Ok(0));
+    assert_eq!(SafeRange::<f32, u32>::MIN, -0.0);
+    assert_eq!(SafeRange::<f64, u32>::MIN, -0.9999999999999999);
+}
+
+#[test]
+fn test_128_bit_bounds() {
+    // These are for clamping before an `as` cast, which saturates.
+    assert_eq!(SafeRange::<f32, i128>::MIN as i128, i128::MIN);
+    assert!((SafeRange::<f32, i128>::MAX as i128) < i128::MAX);
+    assert_eq!(SafeRange::<f32, u128>::MAX, f32::MAX);
+    assert_eq!(
+        SafeRange::<f64, u128>::MAX as u128,
+        u128::MAX - (1 << 75) + 1
+    );
+    assert_eq!(
+        SafeRange::<f64, i128, RoundToNearest>::MIN,
+        -(2f64.powi(127))
+    );
+}
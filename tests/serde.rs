@@ -0,0 +1,106 @@
+#![cfg(feature = "serde")]
+
+use conv2::*;
+// The glob import must not shadow the `serde` crate.
+use serde::Deserialize;
+
+#[test]
+fn test_error_roundtrip() {
+    let err = GeneralError::PosOverflow(300i32);
+    let json = serde_json::to_string(&err).unwrap();
+    assert_eq!(json, r#"{"PosOverflow":300}"#);
+    assert_eq!(
+        serde_json::from_str::<GeneralError<i32>>(&json).unwrap(),
+        err
+    );
+
+    let err = FloatError::NotANumber(1.5f64);
+    let json = serde_json::to_string(&err).unwrap();
+    assert_eq!(serde_json::from_str::<FloatError<f64>>(&json).unwrap(), err);
+
+    let err = RangeError::NegOverflow(-1i8);
+    let json = serde_json::to_string(&err).unwrap();
+    assert_eq!(serde_json::from_str::<RangeError<i8>>(&json).unwrap(), err);
+
+    let json = serde_json::to_string(&NegOverflow(-1i64)).unwrap();
+    assert_eq!(json, "-1");
+    assert_eq!(
+        serde_json::from_str::<NegOverflow<i64>>(&json).unwrap(),
+        NegOverflow(-1)
+    );
+    let json = serde_json::to_string(&PosOverflow(2u8)).unwrap();
+    assert_eq!(
+        serde_json::from_str::<PosOverflow<u8>>(&json).unwrap(),
+        PosOverflow(2)
+    );
+    let json = serde_json::to_string(&Unrepresentable(3u16)).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Unrepresentable<u16>>(&json).unwrap(),
+        Unrepresentable(3)
+    );
+
+    let json = serde_json::to_string(&GeneralErrorKind::Unrepresentable).unwrap();
+    assert_eq!(json, r#""Unrepresentable""#);
+    assert_eq!(
+        serde_json::from_str::<GeneralErrorKind>(&json).unwrap(),
+        GeneralErrorKind::Unrepresentable
+    );
+    let json = serde_json::to_string(&RangeErrorKind::PosOverflow).unwrap();
+    assert_eq!(
+        serde_json::from_str::<RangeErrorKind>(&json).unwrap(),
+        RangeErrorKind::PosOverflow
+    );
+
+    assert!(serde_json::from_str::<NoError>(r#""Anything""#).is_err());
+
+    let err = 300i32.value_as::<u8>().with_context().unwrap_err();
+    let json = serde_json::to_string(&err).unwrap();
+    assert_eq!(
+        json,
+        r#"{"kind":"PosOverflow","value":300,"min":0,"max":255}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<ContextError<i32, u8>>(&json).unwrap(),
+        err
+    );
+
+    let err = ConversionError::new::<u8, _, _>(PosOverflow(300i32));
+    assert_eq!(
+        serde_json::to_string(&err).unwrap(),
+        r#"{"kind":"PosOverflow","value":"300","src":"i32","dst":"u8"}"#
+    );
+}
+
+#[derive(Debug, Deserialize)]
+struct Record {
+    #[serde(deserialize_with = "conv2::de::value_from::i64")]
+    port: u16,
+    #[serde(deserialize_with = "conv2::de::approx_from::f64::<conv2::RoundToZero, _, _>")]
+    level: i8,
+}
+
+#[test]
+fn test_deserialize_with() {
+    let r: Record = serde_json::from_str(r#"{"port": 443, "level": -3.9}"#).unwrap();
+    assert_eq!(r.port, 443);
+    assert_eq!(r.level, -3);
+
+    let err = serde_json::from_str::<Record>(r#"{"port": -1, "level": 0}"#).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("conversion resulted in negative overflow: -1"));
+
+    let err = serde_json::from_str::<Record>(r#"{"port": 70000, "level": 0}"#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "conversion resulted in positive overflow: 70000 at line 1 column 14"
+    );
+
+    let err = serde_json::from_str::<Record>(r#"{"port": 1, "level": 128.0}"#).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("conversion resulted in positive overflow: 128"));
+
+    // The source type still has to deserialize successfully.
+    assert!(serde_json::from_str::<Record>(r#"{"port": "1", "level": 0}"#).is_err());
+}
@@ -0,0 +1,182 @@
+use conv2::stochastic::{RandomSource, SplitMix64, StochasticFrom, StochasticInto};
+use conv2::*;
+
+/// Always returns the same value.
+struct Fixed(u64);
+
+impl RandomSource for Fixed {
+    fn next_u64(&mut self) -> u64 {
+        self.0
+    }
+}
+
+/// Counts how many values were drawn.
+struct Counting(u64);
+
+impl RandomSource for Counting {
+    fn next_u64(&mut self) -> u64 {
+        self.0 += 1;
+        0
+    }
+}
+
+#[test]
+fn test_split_mix_64() {
+    // Reference values for a zero seed.
+    let mut rng = SplitMix64::new(0);
+    assert_eq!(rng.next_u64(), 0xe220_a839_7b1d_cdaf);
+    assert_eq!(rng.next_u64(), 0x6e78_9e6a_a1b9_65f4);
+    assert_eq!(rng.next_u64(), 0x06c4_5d18_8009_454f);
+
+    let (mut a, mut b) = (SplitMix64::new(7), SplitMix64::new(7));
+    let xs: Vec<i32> = (0..100)
+        .map(|_| 0.5f32.approx_with_rng(&mut a).unwrap())
+        .collect();
+    let ys: Vec<i32> = (0..100)
+        .map(|_| 0.5f32.approx_with_rng(&mut b).unwrap())
+        .collect();
+    assert_eq!(xs, ys);
+}
+
+#[test]
+fn test_float_to_int() {
+    // The smallest random value always rounds up, and the largest rounds down.
+    let (mut up, mut down) = (Fixed(0), Fixed(u64::MAX));
+    assert_eq!(2.25f64.approx_with_rng(&mut up), Ok(3i32));
+    assert_eq!(2.25f64.approx_with_rng(&mut down), Ok(2i32));
+    assert_eq!((-2.25f32).approx_with_rng(&mut up), Ok(-2i8));
+    assert_eq!((-2.25f32).approx_with_rng(&mut down), Ok(-3i8));
+    assert_eq!(0.999f64.approx_with_rng(&mut down), Ok(0u64));
+
+    // A value halfway through the range of random values.
+    let mut half = Fixed(1 << 63);
+    assert_eq!(0.5f64.approx_with_rng(&mut half), Ok(0u8));
+    assert_eq!(0.5000001f64.approx_with_rng(&mut half), Ok(1u8));
+
+    // Exact values are not rounded, and draw nothing.
+    let mut counting = Counting(0);
+    assert_eq!(5.0f32.approx_with_rng(&mut counting), Ok(5u16));
+    assert_eq!((-0.0f64).approx_with_rng(&mut counting), Ok(0u16));
+    assert_eq!(
+        1e18f64.approx_with_rng(&mut counting),
+        Ok(1_000_000_000_000_000_000i64)
+    );
+    assert_eq!(counting.0, 0);
+    assert_eq!(5.5f32.approx_with_rng(&mut counting), Ok(6u16));
+    assert_eq!(counting.0, 1);
+}
+
+#[test]
+fn test_float_to_int_range() {
+    // Values are accepted only if both directions are in range.
+    let mut rng = Fixed(0);
+    assert_eq!(255.0f64.approx_with_rng(&mut rng), Ok(255u8));
+    assert_eq!(
+        u8::stochastic_from(255.25f64, &mut rng),
+        Err(FloatError::PosOverflow(255.25))
+    );
+    assert_eq!(
+        u8::stochastic_from(-0.5f32, &mut Fixed(u64::MAX)),
+        Err(FloatError::NegOverflow(-0.5))
+    );
+    assert_eq!(
+        i8::stochastic_from(-128.5f64, &mut rng),
+        Err(FloatError::NegOverflow(-128.5))
+    );
+    assert!(matches!(
+        i32::stochastic_from(f32::NAN, &mut rng),
+        Err(FloatError::NotANumber(_))
+    ));
+    assert_eq!(
+        u64::stochastic_from(f64::INFINITY, &mut rng),
+        Err(FloatError::PosOverflow(f64::INFINITY))
+    );
+
+    macro_rules! check_bounds {
+        ($src:ident => $($dst:ident),*) => {
+            $(
+                let min = SafeRange::<$src, $dst, RoundToNegInf>::MIN;
+                let max = SafeRange::<$src, $dst, RoundToPosInf>::MAX;
+                for mut rng in [Fixed(0), Fixed(u64::MAX)] {
+                    assert!(<$dst>::stochastic_from(min, &mut rng).is_ok());
+                    assert!(<$dst>::stochastic_from(max, &mut rng).is_ok());
+                }
+            )*
+        };
+    }
+    check_bounds!(f32 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+    check_bounds!(f64 => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+}
+
+#[test]
+fn test_float_to_int_unbiased() {
+    let mut rng = SplitMix64::new(1);
+    for &x in &[0.1f64, 0.25, 0.5, 0.9, -3.7, 100.375] {
+        let n = 100_000;
+        let sum: i64 = (0..n)
+            .map(|_| i64::stochastic_from(x, &mut rng).unwrap())
+            .sum();
+        let mean = sum as f64 / n as f64;
+        assert!((mean - x).abs() < 0.01, "{} averaged to {}", x, mean);
+    }
+}
+
+#[test]
+fn test_f64_to_f32() {
+    let x = 1.0 + f64::EPSILON;
+    let above = 1.0 + f32::EPSILON;
+    assert_eq!(x.approx_with_rng(&mut Fixed(0)), Ok(above));
+    assert_eq!(x.approx_with_rng(&mut Fixed(u64::MAX)), Ok(1.0f32));
+
+    // Tiny values round to zero or the smallest subnormal.
+    let tiny = f32::from_bits(1);
+    assert_eq!(1e-50f64.approx_with_rng(&mut Fixed(0)), Ok(tiny));
+    assert_eq!(1e-50f64.approx_with_rng(&mut Fixed(u64::MAX)), Ok(0.0f32));
+    assert_eq!((-1e-50f64).approx_with_rng(&mut Fixed(0)), Ok(-0.0f32));
+    assert_eq!((-1e-50f64).approx_with_rng(&mut Fixed(u64::MAX)), Ok(-tiny));
+
+    // Exact values, infinities and NaN pass through.
+    let mut counting = Counting(0);
+    assert_eq!((0.1f32 as f64).approx_with_rng(&mut counting), Ok(0.1f32));
+    assert_eq!(
+        f64::NEG_INFINITY.approx_with_rng(&mut counting),
+        Ok(f32::NEG_INFINITY)
+    );
+    assert!(f32::stochastic_from(f64::NAN, &mut counting)
+        .unwrap()
+        .is_nan());
+    assert_eq!(
+        (f32::MAX as f64).approx_with_rng(&mut counting),
+        Ok(f32::MAX)
+    );
+    assert_eq!(counting.0, 0);
+
+    assert_eq!(
+        f32::stochastic_from(f64::MAX, &mut counting),
+        Err(RangeError::PosOverflow(f64::MAX))
+    );
+    assert_eq!(
+        f32::stochastic_from(f32::MIN as f64 * 1.0000001, &mut counting),
+        Err(RangeError::NegOverflow(f32::MIN as f64 * 1.0000001))
+    );
+
+    // On average, the result equals the input.
+    let mut rng = SplitMix64::new(2);
+    let x = 1.0 + 0.3 * f32::EPSILON as f64;
+    let n = 100_000;
+    let ups = (0..n)
+        .filter(|_| f32::stochastic_from(x, &mut rng).unwrap() != 1.0)
+        .count();
+    let p = ups as f64 / n as f64;
+    assert!((p - 0.3).abs() < 0.01, "rounded up {} of the time", p);
+}
+
+#[test]
+fn test_by_reference() {
+    // A `&mut dyn RandomSource` can be used too.
+    let mut rng = SplitMix64::new(3);
+    let rng: &mut dyn RandomSource = &mut rng;
+    let x: Result<u8, _> = 1.5f32.approx_with_rng(rng);
+    assert!(x == Ok(1) || x == Ok(2));
+    assert_eq!(u8::stochastic_from(7u8, rng), Ok(7));
+}
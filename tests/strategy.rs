@@ -0,0 +1,118 @@
+#![cfg(feature = "proptest")]
+
+use conv2::misc::ConversionInfo;
+use conv2::strategy::{boundary_values, Boundaries};
+use conv2::*;
+use proptest::prelude::*;
+use proptest::strategy::ValueTree;
+use proptest::test_runner::TestRunner;
+
+/// Draws a batch of values from `boundary_values::<Src, Dst>()`.
+fn sample<Src, Dst>() -> Vec<Src>
+where
+    Src: conv2::strategy::Edge + Arbitrary,
+    Dst: Boundaries<Src>,
+{
+    let strategy = boundary_values::<Src, Dst>();
+    let mut runner = TestRunner::deterministic();
+    (0..1000)
+        .map(|_| strategy.new_tree(&mut runner).unwrap().current())
+        .collect()
+}
+
+#[test]
+fn test_hits_both_sides() {
+    let values = sample::<f64, u8>();
+    assert!(values.contains(&SafeRange::<f64, u8>::MAX));
+    assert!(values.contains(&256.0));
+    assert!(values.contains(&SafeRange::<f64, u8, RoundToNearest>::MAX));
+    assert!(values.iter().any(|x| x.is_nan()));
+    assert!(values.contains(&f64::from_bits(1)));
+
+    let values = sample::<i64, i8>();
+    assert!(values.contains(&-128));
+    assert!(values.contains(&-129));
+    assert!(values.contains(&127));
+    assert!(values.contains(&128));
+    assert!(values.contains(&i64::MIN));
+
+    let values = sample::<u32, f32>();
+    assert!(values.contains(&16_777_216));
+    assert!(values.contains(&16_777_217));
+    assert!(values.contains(&u32::MAX));
+
+    let values = sample::<f64, f32>();
+    assert!(values.contains(&(f32::MAX as f64)));
+    assert!(values.iter().any(|&x| x > f32::MAX as f64 && x.is_finite()));
+}
+
+#[test]
+fn test_boundaries() {
+    assert_eq!(<i8 as Boundaries<i64>>::boundaries(), vec![-128, 127]);
+    assert_eq!(<u64 as Boundaries<u64>>::boundaries(), vec![0, u64::MAX]);
+    assert_eq!(
+        <f32 as Boundaries<i32>>::boundaries(),
+        vec![-16_777_216, 16_777_216]
+    );
+    assert!(<i32 as Boundaries<f32>>::boundaries().contains(&-2_147_483_648.0));
+}
+
+/// A type converted from `i32`, with a custom implementation.
+struct Percent;
+
+impl Boundaries<i32> for Percent {
+    fn boundaries() -> Vec<i32> {
+        vec![0, 100]
+    }
+}
+
+#[test]
+fn test_custom_boundaries() {
+    let values = sample::<i32, Percent>();
+    assert!(values.contains(&-1));
+    assert!(values.contains(&101));
+}
+
+macro_rules! check_float_to_int {
+    ($name:ident, $src:ident => $dst:ident) => {
+        proptest! {
+            #[test]
+            fn $name(x in boundary_values::<$src, $dst>()) {
+                macro_rules! check {
+                    ($scheme:ident) => {{
+                        let range = SafeRange::<$src, $dst, $scheme>::MIN..=SafeRange::<$src, $dst, $scheme>::MAX;
+                        let result = <$dst as ApproxFrom<$src, $scheme>>::approx_from(x);
+                        prop_assert_eq!(result.is_ok(), range.contains(&x), "{:?} with {}", x, stringify!($scheme));
+                    }};
+                }
+                check!(DefaultApprox);
+                check!(RoundToZero);
+                check!(RoundToNearest);
+                check!(RoundToNegInf);
+                check!(RoundToPosInf);
+            }
+        }
+    };
+}
+
+check_float_to_int!(test_f32_to_i32, f32 => i32);
+check_float_to_int!(test_f32_to_u64, f32 => u64);
+check_float_to_int!(test_f64_to_i8, f64 => i8);
+check_float_to_int!(test_f64_to_usize, f64 => usize);
+
+macro_rules! check_value {
+    ($name:ident, $src:ident => $dst:ident) => {
+        proptest! {
+            #[test]
+            fn $name(x in boundary_values::<$src, $dst>()) {
+                let range = <$dst as ConversionInfo<$src>>::SRC_MIN..=<$dst as ConversionInfo<$src>>::SRC_MAX;
+                prop_assert_eq!(<$dst>::value_from(x).is_ok(), range.contains(&x));
+            }
+        }
+    };
+}
+
+check_value!(test_i64_to_i8, i64 => i8);
+check_value!(test_i8_to_u32, i8 => u32);
+check_value!(test_u64_to_isize, u64 => isize);
+check_value!(test_i64_to_f64, i64 => f64);
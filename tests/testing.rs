@@ -0,0 +1,85 @@
+#![cfg(feature = "quickcheck")]
+
+use conv2::testing::*;
+use conv2::*;
+
+#[test]
+fn test_value_roundtrip() {
+    check_value_roundtrip::<i8, u8>();
+    check_value_roundtrip::<i32, u16>();
+    check_value_roundtrip::<i64, u64>();
+    check_value_roundtrip::<usize, isize>();
+}
+
+#[test]
+fn test_approx_bounds() {
+    check_approx_bounds::<i64, i8, DefaultApprox>();
+    check_approx_bounds::<u32, i16, DefaultApprox>();
+    check_approx_bounds::<i64, f32, DefaultApprox>();
+    check_approx_bounds::<f32, u8, DefaultApprox>();
+    check_approx_bounds::<f64, i32, RoundToZero>();
+    check_approx_bounds::<f64, i8, RoundToNearest>();
+    check_approx_bounds::<f32, u16, RoundToNegInf>();
+    check_approx_bounds::<f64, i64, RoundToPosInf>();
+}
+
+#[test]
+fn test_error_kind_consistency() {
+    check_error_kind_consistency::<i64, u32>();
+    check_error_kind_consistency::<u8, i8>();
+    check_error_kind_consistency::<isize, i16>();
+    check_error_kind_consistency::<i64, f32>();
+    check_error_kind_consistency::<u64, f64>();
+}
+
+/// A type whose conversions break every law checked here.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+struct Broken(u8);
+
+impl ValueFrom<i16> for Broken {
+    type Err = RangeError<i16>;
+    fn value_from(src: i16) -> Result<Self, Self::Err> {
+        match src {
+            0..=255 => Ok(Broken(src as u8 / 2)),
+            _ => Err(RangeError::PosOverflow(0)),
+        }
+    }
+}
+
+impl ValueFrom<Broken> for i16 {
+    type Err = NoError;
+    fn value_from(src: Broken) -> Result<Self, Self::Err> {
+        Ok(src.0 as i16)
+    }
+}
+
+impl ApproxFrom<i16> for Broken {
+    type Err = RangeError<i16>;
+    fn approx_from(src: i16) -> Result<Self, Self::Err> {
+        Ok(Broken(255 - src as u8))
+    }
+}
+
+impl quickcheck::Arbitrary for Broken {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Broken(u8::arbitrary(g))
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_broken_roundtrip() {
+    check_value_roundtrip::<i16, Broken>();
+}
+
+#[test]
+#[should_panic]
+fn test_broken_approx_bounds() {
+    check_approx_bounds::<i16, Broken, DefaultApprox>();
+}
+
+#[test]
+#[should_panic]
+fn test_broken_error_kinds() {
+    check_error_kind_consistency::<i16, Broken>();
+}
@@ -1,5 +1,8 @@
-use conv2_benchmarks::{asm_f32_i32, conv2_f32_u32, num_f32_u32};
-use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use conv2_benchmarks::{
+    asm_f32_i32, asm_f32_i32_slice, conv2_batch_f32_i32, conv2_batch_saturate_f32_i32,
+    conv2_each_f32_i32, conv2_each_saturate_f32_i32, conv2_f32_u32, num_f32_u32,
+};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
 
 fn make_input() -> Vec<f32> {
     let mut input = Vec::<f32>::new();
@@ -19,5 +22,29 @@ pub fn convert_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, convert_benchmark);
+/// Compares converting one element at a time with the functions in
+/// `conv2::batch`, writing into a preallocated output.
+pub fn slice_benchmark(c: &mut Criterion) {
+    // The buffers are reused, since every iteration writes the same values,
+    // and kept small enough to stay in cache, so that memory bandwidth does
+    // not hide the cost of the conversions.
+    let mut input = make_input();
+    input.truncate(4096);
+    let mut output = vec![0; input.len()];
+    type SliceFn = fn(&[f32], &mut [i32]);
+    let functions: [(&str, SliceFn); 5] = [
+        ("asm_f32_i32_slice", asm_f32_i32_slice),
+        ("conv2_each_f32_i32", conv2_each_f32_i32),
+        ("conv2_batch_f32_i32", conv2_batch_f32_i32),
+        ("conv2_each_saturate_f32_i32", conv2_each_saturate_f32_i32),
+        ("conv2_batch_saturate_f32_i32", conv2_batch_saturate_f32_i32),
+    ];
+    for (name, function) in functions {
+        c.bench_function(name, |b| {
+            b.iter(|| function(black_box(&input), black_box(&mut output)))
+        });
+    }
+}
+
+criterion_group!(benches, convert_benchmark, slice_benchmark);
 criterion_main!(benches);
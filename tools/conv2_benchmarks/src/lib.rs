@@ -3,6 +3,7 @@
 
 use std::arch::x86_64::{_mm_cvttss_si32, _mm_loadu_ps};
 
+use conv2::batch::{approx_slice, saturate_slice};
 use conv2::{
     ConvAsUtil as _, ConvUtil as _, DefaultApprox, RoundToNearest, Saturate as _, ValueFrom,
     ValueInto as _,
@@ -42,6 +43,36 @@ pub fn conv2_f32_u32(input: Vec<f32>) -> Vec<u32> {
         .collect()
 }
 
+// The functions below write into a preallocated slice, so that only the
+// conversions themselves are measured. Like the functions in `conv2::batch`,
+// the per-element versions stop at the first failure.
+
+pub fn conv2_each_f32_i32(input: &[f32], output: &mut [i32]) {
+    for (output, &input) in output.iter_mut().zip(input) {
+        match input.approx_as::<i32>() {
+            Ok(value) => *output = value,
+            Err(_) => return,
+        }
+    }
+}
+
+pub fn conv2_batch_f32_i32(input: &[f32], output: &mut [i32]) {
+    approx_slice::<_, _, DefaultApprox>(input, output).unwrap();
+}
+
+pub fn conv2_each_saturate_f32_i32(input: &[f32], output: &mut [i32]) {
+    for (output, &input) in output.iter_mut().zip(input) {
+        match input.approx_as::<i32>().saturate() {
+            Ok(value) => *output = value,
+            Err(_) => return,
+        }
+    }
+}
+
+pub fn conv2_batch_saturate_f32_i32(input: &[f32], output: &mut [i32]) {
+    saturate_slice::<_, _, DefaultApprox>(input, output).unwrap();
+}
+
 /// Convert f32 to i64 using the CVTTSS2SI instruction. If the input f32 is out of range of the output i64, then the result is i64::MIN.
 #[inline(always)]
 fn f32_to_i32(float: f32) -> i32 {
@@ -56,3 +87,9 @@ fn f32_to_i32(float: f32) -> i32 {
 pub fn asm_f32_i32(input: Vec<f32>) -> Vec<i32> {
     input.into_iter().map(f32_to_i32).collect()
 }
+
+pub fn asm_f32_i32_slice(input: &[f32], output: &mut [i32]) {
+    for (output, &input) in output.iter_mut().zip(input) {
+        *output = f32_to_i32(input);
+    }
+}
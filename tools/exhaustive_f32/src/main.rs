@@ -0,0 +1,254 @@
+//! Checks every `ApproxFrom<f32, Scheme>` conversion into every integer type,
+//! for every one of the 2^32 `f32` bit patterns.
+//!
+//! Each result is compared against a reference that decodes the float's bits
+//! and rounds using exact integer arithmetic. Mismatches are reported with the
+//! hex-float form of the input.
+//!
+//! This takes a while, so it is not part of the test suite. Run it with
+//! optimizations:
+//!
+//! ```text
+//! cargo run --release                 # every f32
+//! cargo run --release -- --step 997   # every 997th bit pattern, for a quick check
+//! ```
+
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+
+use conv2::{
+    ApproxFrom, DefaultApprox, FloatError, RoundToNearest, RoundToNegInf, RoundToPosInf,
+    RoundToZero,
+};
+use hexfloat2::HexFloat;
+
+/// Mismatches reported per conversion, before the rest are only counted.
+const MAX_REPORTS: u64 = 10;
+
+/// The outcome of a conversion, independent of the integer type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Outcome {
+    Ok(i128),
+    NegOverflow,
+    PosOverflow,
+    NotANumber,
+}
+
+impl From<Result<i128, FloatError<f32>>> for Outcome {
+    fn from(result: Result<i128, FloatError<f32>>) -> Self {
+        match result {
+            Ok(v) => Outcome::Ok(v),
+            Err(FloatError::NegOverflow(_)) => Outcome::NegOverflow,
+            Err(FloatError::PosOverflow(_)) => Outcome::PosOverflow,
+            Err(FloatError::NotANumber(_)) => Outcome::NotANumber,
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Round {
+    Trunc,
+    Nearest,
+    Floor,
+    Ceil,
+}
+
+/// A finite float decoded as `(-1)^negative * magnitude * 2^exp`.
+struct Decoded {
+    negative: bool,
+    magnitude: u128,
+    exp: i32,
+}
+
+fn decode(bits: u32) -> Option<Decoded> {
+    let negative = bits >> 31 != 0;
+    let biased = ((bits >> 23) & 0xff) as i32;
+    let fraction = (bits & 0x7f_ffff) as u128;
+    match biased {
+        0xff => None,
+        0 => Some(Decoded {
+            negative,
+            magnitude: fraction,
+            exp: -149,
+        }),
+        _ => Some(Decoded {
+            negative,
+            magnitude: fraction | 1 << 23,
+            exp: biased - 150,
+        }),
+    }
+}
+
+/// Converts a float to an integer in `[min, max]`, using only integer
+/// arithmetic.
+///
+/// `int_bits` is the width of the integer type, which decides whether
+/// truncation rejects every negative value (see
+/// `safe_float_to_int::trunc_rejects_negatives`).
+fn reference(bits: u32, round: Round, min: i128, max: i128, int_bits: u32) -> Outcome {
+    let d = match decode(bits) {
+        Some(d) => d,
+        None if bits & 0x7f_ffff != 0 => return Outcome::NotANumber,
+        None if bits >> 31 != 0 => return Outcome::NegOverflow,
+        None => return Outcome::PosOverflow,
+    };
+
+    // Split the magnitude into its integer part and the fraction. A finite
+    // `f32` is below 2^128, so the integer part always fits in a `u128`.
+    let (int, frac, half) = if d.exp >= 0 {
+        (d.magnitude << d.exp, 0, 1)
+    } else if d.exp > -64 {
+        let shift = -d.exp as u32;
+        let frac = d.magnitude & ((1 << shift) - 1);
+        (d.magnitude >> shift, frac, 1 << (shift - 1))
+    } else {
+        // The magnitude is below 2^24, so the value is below 2^-40.
+        (0, d.magnitude, u128::MAX)
+    };
+
+    if matches!(round, Round::Trunc)
+        && d.negative
+        && d.magnitude != 0
+        && safe_float_to_int::trunc_rejects_negatives(32, int_bits, min < 0)
+    {
+        return Outcome::NegOverflow;
+    }
+
+    let round_up = match round {
+        Round::Trunc => false,
+        Round::Nearest => frac >= half,
+        Round::Floor => d.negative && frac != 0,
+        Round::Ceil => !d.negative && frac != 0,
+    };
+    let magnitude = int + round_up as u128;
+
+    // Anything of 2^127 or more is out of range for every target.
+    if magnitude >= 1 << 127 {
+        return if d.negative {
+            Outcome::NegOverflow
+        } else {
+            Outcome::PosOverflow
+        };
+    }
+    let value = if d.negative {
+        -(magnitude as i128)
+    } else {
+        magnitude as i128
+    };
+    if value < min {
+        Outcome::NegOverflow
+    } else if value > max {
+        Outcome::PosOverflow
+    } else {
+        Outcome::Ok(value)
+    }
+}
+
+/// A single conversion to check.
+struct Check {
+    name: &'static str,
+    round: Round,
+    min: i128,
+    max: i128,
+    bits: u32,
+    convert: fn(f32) -> Outcome,
+    mismatches: AtomicU64,
+}
+
+macro_rules! checks {
+    ($($ity:ident),*) => {
+        vec![$(
+            checks!(@ $ity, DefaultApprox, Round::Trunc),
+            checks!(@ $ity, RoundToZero, Round::Trunc),
+            checks!(@ $ity, RoundToNearest, Round::Nearest),
+            checks!(@ $ity, RoundToNegInf, Round::Floor),
+            checks!(@ $ity, RoundToPosInf, Round::Ceil),
+        )*]
+    };
+    (@ $ity:ident, $scheme:ident, $round:expr) => {
+        Check {
+            name: concat!("f32 -> ", stringify!($ity), " by ", stringify!($scheme)),
+            round: $round,
+            min: $ity::MIN as i128,
+            max: $ity::MAX as i128,
+            bits: $ity::BITS,
+            convert: |x| <$ity as ApproxFrom<f32, $scheme>>::approx_from(x).map(|v| v as i128).into(),
+            mismatches: AtomicU64::new(0),
+        }
+    };
+}
+
+fn run(checks: &[Check], bits: impl Iterator<Item = u32>) {
+    for bits in bits {
+        let x = f32::from_bits(bits);
+        for check in checks {
+            let actual = (check.convert)(x);
+            let expected = reference(bits, check.round, check.min, check.max, check.bits);
+            if actual != expected {
+                let count = check.mismatches.fetch_add(1, Ordering::Relaxed);
+                if count < MAX_REPORTS {
+                    println!(
+                        "{}: {} ({:#010x}): got {:?}, expected {:?}",
+                        check.name,
+                        HexFloat(x),
+                        bits,
+                        actual,
+                        expected
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let step = match args.as_slice() {
+        [] => 1,
+        [flag, n] if flag == "--step" => match n.parse::<u32>() {
+            Ok(n) if n > 0 => n,
+            _ => {
+                eprintln!("--step must be a positive integer");
+                return ExitCode::FAILURE;
+            }
+        },
+        _ => {
+            eprintln!("usage: exhaustive_f32 [--step N]");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let checks = checks!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+    let threads = thread::available_parallelism().map_or(1, |n| n.get()) as u64;
+    let count = (1u64 << 32).div_ceil(step as u64);
+    println!(
+        "checking {} conversions for {} f32 values on {} threads",
+        checks.len(),
+        count,
+        threads
+    );
+
+    thread::scope(|scope| {
+        for t in 0..threads {
+            let checks = &checks;
+            let (start, end) = (count * t / threads, count * (t + 1) / threads);
+            scope.spawn(move || run(checks, (start..end).map(|i| (i * step as u64) as u32)));
+        }
+    });
+
+    let mut failed = false;
+    for check in &checks {
+        let mismatches = check.mismatches.load(Ordering::Relaxed);
+        if mismatches > 0 {
+            println!("{}: {} mismatches", check.name, mismatches);
+            failed = true;
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        println!("all conversions match");
+        ExitCode::SUCCESS
+    }
+}
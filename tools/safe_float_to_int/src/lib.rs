@@ -0,0 +1,14 @@
+//! The rules shared by `safe_float_to_int` and `exhaustive_f32`, so that the
+//! generated table and the exhaustive check cannot disagree.
+
+/// Returns `true` if truncating a float with `float_bits` bits into an
+/// integer type rejects every negative value, rather than only those whose
+/// truncated value is negative.
+///
+/// Truncating into an unsigned type at least as wide as the float has always
+/// rejected negative values, even those that truncate to zero. Changing that
+/// would change which conversions succeed, so the bounds keep `-0.0` for
+/// those (except `u128`, which conv2 does not convert into).
+pub fn trunc_rejects_negatives(float_bits: u32, int_bits: u32, signed: bool) -> bool {
+    !signed && int_bits >= float_bits && int_bits < 128
+}
@@ -1,76 +1,338 @@
-//! Experimentally derives, for each (int, float) pair, the largest and
-//! smallest integer values that survive round-tripping through the float
-//! types.
+//! Generates `src/limits.rs`, which holds the exact bounds of conv2's
+//! float-to-int conversions.
 //!
-//! This is to verify *exactly* what the safe range for float-to-int
-//! conversions is.
+//! For each (float, int) pair and each rounding scheme, this searches for the
+//! smallest and largest float values whose rounded value fits in the integer
+//! type. The search runs over every representable float between zero and
+//! infinity, so no seed values are needed.
+//!
+//! Usage:
+//!
+//! ```text
+//! cargo run              # rewrite src/limits.rs
+//! cargo run -- --check   # fail if src/limits.rs is out of date
+//! ```
 
-use hexfloat2::HexFloat;
-use ieee754::Ieee754;
+use std::fmt::{Debug, Write as _};
+use std::path::PathBuf;
+use std::process::ExitCode;
 
-macro_rules! limits {
-    ($src:ty => $dst:ident; < $min:expr, > $max:expr) => {
-        limits!($src => $dst; < $min);
-        limits!($src => $dst; > $max);
-    };
-    ($src:ty => $dst:ident; > $max:expr) => {
-        {
-            let mut cur: $src = $max;
-            if ((cur as $dst) as $src) != cur {
-                panic!("safe {} max: not found; initial limit too high!", stringify!($src => $dst));
+/// The operations needed to search a float type.
+trait Float: Copy + Debug + PartialOrd {
+    const NAME: &'static str;
+    const BITS: u32;
+    const INFINITY: Self;
+    const NEG_INFINITY: Self;
+
+    /// Maps floats to integers, preserving their order (with `-0.0` just
+    /// below `0.0`).
+    fn to_key(self) -> i64;
+    fn from_key(key: i64) -> Self;
+
+    /// Returns `2^exp`, or infinity if that is out of range.
+    fn pow2(exp: u32) -> Self;
+    fn neg(self) -> Self;
+    fn zero() -> Self;
+
+    fn trunc(self) -> Self;
+    fn round(self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+}
+
+macro_rules! float {
+    ($fty:ident, $ity:ident, $uty:ident) => {
+        impl Float for $fty {
+            const NAME: &'static str = stringify!($fty);
+            const BITS: u32 = $uty::BITS;
+            const INFINITY: Self = $fty::INFINITY;
+            const NEG_INFINITY: Self = $fty::NEG_INFINITY;
+
+            fn to_key(self) -> i64 {
+                let bits = self.to_bits() as $ity;
+                (if bits < 0 { !(bits & $ity::MAX) } else { bits }) as i64
             }
-            loop {
-                let next = (cur + 1.0).max(cur.next());
-                let next_int = next as $dst;
-                let next_rnd = next_int as $src;
-                if next_rnd != next {
-                    println!("safe {} max: {}, {:e}, {:}",
-                        stringify!($src => $dst), cur, cur, HexFloat(cur));
-                    break;
-                } else {
-                    cur = next;
-                }
+
+            fn from_key(key: i64) -> Self {
+                let key = key as $ity;
+                $fty::from_bits((if key < 0 { !key | $ity::MIN } else { key }) as $uty)
             }
-        }
-    };
-    ($src:ty => $dst:ident; < $min:expr) => {
-        {
-            let mut cur: $src = $min;
-            if ((cur as $dst) as $src) != cur {
-                panic!("safe {} min: not found; initial limit too low!", stringify!($src => $dst));
+
+            fn pow2(exp: u32) -> Self {
+                (2.0 as $fty).powi(exp as i32)
             }
-            loop {
-                let next = (cur - 1.0).min(cur.prev());
-                let next_int = next as $dst;
-                let next_rnd = next_int as $src;
-                if next_rnd != next {
-                    println!("safe {} min: {:+}, {:+e}, {}",
-                        stringify!($src => $dst), cur, cur, HexFloat(cur));
-                    break;
-                } else {
-                    cur = next;
-                }
+
+            fn neg(self) -> Self {
+                -self
+            }
+
+            fn zero() -> Self {
+                0.0
+            }
+
+            fn trunc(self) -> Self {
+                $fty::trunc(self)
             }
+
+            fn round(self) -> Self {
+                $fty::round(self)
+            }
+
+            fn floor(self) -> Self {
+                $fty::floor(self)
+            }
+
+            fn ceil(self) -> Self {
+                $fty::ceil(self)
+            }
+        }
+    };
+}
+
+float!(f32, i32, u32);
+float!(f64, i64, u64);
+
+/// The rounding step applied by a scheme.
+#[derive(Copy, Clone)]
+enum Round {
+    Trunc,
+    Nearest,
+    Floor,
+    Ceil,
+}
+
+/// The schemes conv2 implements for float-to-int conversions, in the order
+/// they appear in the table.
+const SCHEMES: &[(&str, Round)] = &[
+    ("DefaultApprox", Round::Trunc),
+    ("RoundToZero", Round::Trunc),
+    ("RoundToNearest", Round::Nearest),
+    ("RoundToNegInf", Round::Floor),
+    ("RoundToPosInf", Round::Ceil),
+];
+
+impl Round {
+    fn apply<F: Float>(self, x: F) -> F {
+        match self {
+            Round::Trunc => x.trunc(),
+            Round::Nearest => x.round(),
+            Round::Floor => x.floor(),
+            Round::Ceil => x.ceil(),
+        }
+    }
+}
+
+/// An integer type, described by its width and signedness.
+struct Int {
+    name: &'static str,
+    bits: u32,
+    signed: bool,
+    cfg: Option<&'static str>,
+}
+
+const fn int(name: &'static str, bits: u32, signed: bool) -> Int {
+    Int {
+        name,
+        bits,
+        signed,
+        cfg: None,
+    }
+}
+
+const fn sized(name: &'static str, bits: u32, signed: bool, width: &'static str) -> Int {
+    Int {
+        name,
+        bits,
+        signed,
+        cfg: Some(width),
+    }
+}
+
+const INTS: &[Int] = &[
+    int("i8", 8, true),
+    int("i16", 16, true),
+    int("i32", 32, true),
+    int("i64", 64, true),
+    int("u8", 8, false),
+    int("u16", 16, false),
+    int("u32", 32, false),
+    int("u64", 64, false),
+    sized("isize", 32, true, "32"),
+    sized("isize", 64, true, "64"),
+    sized("usize", 32, false, "32"),
+    sized("usize", 64, false, "64"),
+    int("i128", 128, true),
+    int("u128", 128, false),
+];
+
+/// Returns the smallest and largest `F` values that convert successfully.
+///
+/// A value converts if its rounded value lies in `[MIN, MAX + 1)`. Both
+/// bounds are exactly representable (or infinite), so no precision is lost
+/// in the comparison.
+fn bounds<F: Float>(int: &Int, round: Round) -> (F, F) {
+    let (min, end) = if int.signed {
+        (F::pow2(int.bits - 1).neg(), F::pow2(int.bits - 1))
+    } else {
+        (F::zero(), F::pow2(int.bits))
+    };
+    let fits = |x: F| {
+        let r = round.apply(x);
+        r >= min && r < end
+    };
+
+    // Both searches keep `fits(lo) != fits(hi)`, with zero always fitting.
+    let (mut lo, mut hi) = (F::zero().to_key(), F::INFINITY.to_key());
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if fits(F::from_key(mid)) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    let max = F::from_key(lo);
+
+    let (mut lo, mut hi) = (F::NEG_INFINITY.to_key(), F::zero().neg().to_key());
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if fits(F::from_key(mid)) {
+            hi = mid;
+        } else {
+            lo = mid;
         }
+    }
+    let min = F::from_key(hi);
+
+    if matches!(round, Round::Trunc)
+        && safe_float_to_int::trunc_rejects_negatives(F::BITS, int.bits, int.signed)
+    {
+        return (F::zero().neg(), max);
+    }
+
+    (min, max)
+}
+
+const HEADER: &str = r#"// @generated by `tools/safe_float_to_int`; do not edit by hand.
+//
+// The exact bounds of the float-to-integer conversions. Some conversions
+// start to lose precision before they reach the min/max values in the
+// destination type, so the bounds are given as floating-point literals.
+// Each bound is also a safe input to `to_int_unchecked` after rounding.
+
+use std::marker::PhantomData;
+
+use crate::{DefaultApprox, RoundToNearest, RoundToNegInf, RoundToPosInf, RoundToZero};
+
+/// The range of `Src` values that convert successfully into `Dst` under the
+/// approximation scheme `Scheme`.
+///
+/// `MIN` and `MAX` are the smallest and largest source values that convert
+/// successfully; both are inclusive. Any value outside this range (or NaN)
+/// fails to convert. These are the exact bounds used by the `ApproxFrom`
+/// implementations, so they can be used to clamp values before converting
+/// them in bulk.
+///
+/// This is provided for every float → integer conversion, under every scheme
+/// it supports, and for every integer → float conversion under
+/// `DefaultApprox` (which cannot fail, so the range is the whole type). The
+/// float → `i128`/`u128` bounds are also provided, for clamping values before
+/// an `as` cast, although this crate does not implement those conversions.
+///
+/// # Examples
+///
+/// ```
+/// # use conv2::*;
+/// assert_eq!(SafeRange::<f32, i32>::MAX, 2_147_483_520.0);
+/// assert_eq!(SafeRange::<f32, i32>::MAX.approx_as::<i32>(), Ok(2_147_483_520));
+///
+/// // Rounding schemes have different bounds.
+/// assert_eq!(SafeRange::<f64, u8, RoundToNearest>::MIN, -0.49999999999999994);
+/// assert_eq!(SafeRange::<f64, u8, RoundToNegInf>::MIN, 0.0);
+/// assert_eq!(SafeRange::<f64, u8, RoundToPosInf>::MAX, 255.0);
+///
+/// let clamp = |x: f64| x.clamp(SafeRange::<f64, u8>::MIN, SafeRange::<f64, u8>::MAX);
+/// assert_eq!(clamp(1e10).approx_as::<u8>(), Ok(255));
+/// assert_eq!(clamp(-1e10).approx_as::<u8>(), Ok(0));
+/// ```
+pub struct SafeRange<Src, Dst, Scheme = DefaultApprox>(PhantomData<fn(Src) -> (Dst, Scheme)>);
+
+macro_rules! safe_range {
+    ($src:ident => $dst:ident: $($scheme:ident [$min:expr, $max:expr],)*) => {
+        $(
+            impl SafeRange<$src, $dst, $scheme> {
+                /// The smallest source value that converts successfully.
+                pub const MIN: $src = $min;
+
+                /// The largest source value that converts successfully.
+                pub const MAX: $src = $max;
+            }
+        )*
     };
 }
 
-fn main() {
-    limits!(f32 => i8; < -120.0, > 120.0);
-    limits!(f32 => i16; < -32000.0, > 32000.0);
-    limits!(f32 => i32; < -2147480000.0, > 2147480000.0);
-    limits!(f32 => i64; < -9223300000000000000.0, > 9223300000000000000.0);
-    limits!(f32 => u8; > 250.0);
-    limits!(f32 => u16; > 64000.0);
-    limits!(f32 => u32; > 4290000000.0);
-    limits!(f32 => u64; > 18446700000000000000.0);
-
-    limits!(f64 => i8; < -120.0, > 120.0);
-    limits!(f64 => i16; < -32000.0, > 32000.0);
-    limits!(f64 => i32; < -2147480000.0, > 2147480000.0);
-    limits!(f64 => i64; < -9223372036854770000.0, > 9223372036854700000.0);
-    limits!(f64 => u8; > 250.0);
-    limits!(f64 => u16; > 64000.0);
-    limits!(f64 => u32; > 4290000000.0);
-    limits!(f64 => u64; > 18446744073709500000.0);
+// Integer-to-float conversions cannot fail.
+item_for_each! {
+    (i8), (i16), (i32), (i64), (isize), (u8), (u16), (u32), (u64), (usize) => {
+        ($ity:ident) => {
+            safe_range! { $ity => f32: DefaultApprox [$ity::MIN, $ity::MAX], }
+            safe_range! { $ity => f64: DefaultApprox [$ity::MIN, $ity::MAX], }
+        };
+    }
+}
+"#;
+
+fn emit_float<F: Float>(out: &mut String) {
+    for int in INTS {
+        out.push('\n');
+        if let Some(width) = int.cfg {
+            writeln!(out, "#[cfg(target_pointer_width = \"{}\")]", width).unwrap();
+        }
+        writeln!(out, "safe_range! {{ {} => {}:", F::NAME, int.name).unwrap();
+        for &(scheme, round) in SCHEMES {
+            let (min, max) = bounds::<F>(int, round);
+            writeln!(out, "    {} [{:?}, {:?}],", scheme, min, max).unwrap();
+        }
+        out.push_str("}\n");
+    }
+}
+
+fn generate() -> String {
+    let mut out = String::from(HEADER);
+    emit_float::<f32>(&mut out);
+    emit_float::<f64>(&mut out);
+    out
+}
+
+fn main() -> ExitCode {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "..", "..", "src", "limits.rs"]
+        .iter()
+        .collect();
+    let generated = generate();
+
+    match std::env::args().nth(1).as_deref() {
+        None => {
+            std::fs::write(&path, generated).expect("failed to write limits.rs");
+            println!("wrote {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Some("--check") => {
+            let current = std::fs::read_to_string(&path).expect("failed to read limits.rs");
+            if current == generated {
+                println!("{} is up to date", path.display());
+                ExitCode::SUCCESS
+            } else {
+                eprintln!(
+                    "{} is out of date; run `cargo run` in tools/safe_float_to_int",
+                    path.display()
+                );
+                ExitCode::FAILURE
+            }
+        }
+        Some(arg) => {
+            eprintln!("unknown argument: {}", arg);
+            eprintln!("usage: safe_float_to_int [--check]");
+            ExitCode::FAILURE
+        }
+    }
 }